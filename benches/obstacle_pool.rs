@@ -0,0 +1,47 @@
+//! Frame time with obstacles spawning as fast as the difficulty curve ever
+//! allows, pooled against spawning and despawning every obstacle.
+//!
+//! Run with `cargo bench --bench obstacle_pool`.
+
+use bevy::prelude::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+use taurc_ui::difficulty::DifficultyProfile;
+use taurc_ui::game::{Player, RunSeed, SPAWN_INTERVAL_MIN};
+use taurc_ui::headless::{advance, boot, headless_app, tap_key, DEFAULT_FRAME_STEP};
+use taurc_ui::pool::ObstaclePool;
+
+const WARMUP_FRAMES: u32 = 10 * 60; // fills the track before measuring
+
+/// A run spawning at `SPAWN_INTERVAL_MIN` from the start, with nobody to
+/// hit so it never ends.
+fn busy_run(pool: ObstaclePool) -> App {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(pool).insert_resource(RunSeed(7));
+    boot(&mut app);
+    tap_key(&mut app, KeyCode::Space);
+
+    let world = app.world_mut();
+    let mut profile = world.resource_mut::<DifficultyProfile>();
+    profile.spawn_interval_base = SPAWN_INTERVAL_MIN;
+    profile.spawn_interval_min = SPAWN_INTERVAL_MIN;
+    let player = world.query_filtered::<Entity, With<Player>>().single(world);
+    world.despawn(player);
+    advance(&mut app, WARMUP_FRAMES);
+    app
+}
+
+fn frame_at_spawn_interval_min(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frame_at_spawn_interval_min");
+    group.bench_function("pooled", |b| {
+        let mut app = busy_run(ObstaclePool::default());
+        b.iter(|| app.update());
+    });
+    group.bench_function("spawn_and_despawn", |b| {
+        let mut app = busy_run(ObstaclePool::without_recycling());
+        b.iter(|| app.update());
+    });
+    group.finish();
+}
+
+criterion_group!(benches, frame_at_spawn_interval_min);
+criterion_main!(benches);
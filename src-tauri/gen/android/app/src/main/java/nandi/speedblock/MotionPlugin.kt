@@ -0,0 +1,69 @@
+package nandi.speedblock
+
+import android.app.Activity
+import android.content.Context
+import android.hardware.Sensor
+import android.hardware.SensorEvent
+import android.hardware.SensorEventListener
+import android.hardware.SensorManager
+import android.view.Surface
+import android.webkit.WebView
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+import kotlin.math.atan2
+
+// Keeps the latest roll of the screen from the gravity sensor (or the
+// accelerometer without one) for the game's tilt steering. Registered by
+// src-tauri/src/motion.rs; the game polls it through `read_tilt`.
+@TauriPlugin
+class MotionPlugin(private val activity: Activity) : Plugin(activity), SensorEventListener {
+  private val sensors = activity.getSystemService(Context.SENSOR_SERVICE) as SensorManager
+  private val gravity: Sensor? =
+    sensors.getDefaultSensor(Sensor.TYPE_GRAVITY) ?: sensors.getDefaultSensor(Sensor.TYPE_ACCELEROMETER)
+
+  @Volatile
+  private var roll: Double? = null
+
+  override fun load(webView: WebView) {
+    listen()
+  }
+
+  override fun onResume() {
+    listen()
+  }
+
+  override fun onPause() {
+    sensors.unregisterListener(this)
+    roll = null
+  }
+
+  private fun listen() {
+    gravity?.let { sensors.registerListener(this, it, SensorManager.SENSOR_DELAY_GAME) }
+  }
+
+  override fun onSensorChanged(event: SensorEvent) {
+    val (x, y) = event.values[0] to event.values[1]
+    // Sensor axes follow the device's natural orientation; turn them to
+    // match the screen's.
+    @Suppress("DEPRECATION")
+    val (screenX, screenY) = when (activity.windowManager.defaultDisplay.rotation) {
+      Surface.ROTATION_90 -> -y to x
+      Surface.ROTATION_180 -> -x to -y
+      Surface.ROTATION_270 -> y to -x
+      else -> x to y
+    }
+    roll = atan2(-screenX.toDouble(), screenY.toDouble())
+  }
+
+  override fun onAccuracyChanged(sensor: Sensor?, accuracy: Int) {}
+
+  @Command
+  fun readTilt(invoke: Invoke) {
+    val reading = JSObject()
+    roll?.let { reading.put("roll", it) }
+    invoke.resolve(reading)
+  }
+}
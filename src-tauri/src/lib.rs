@@ -1,14 +1,210 @@
-// Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::Manager;
+
+mod motion;
+
+const LEADERBOARD_FILE: &str = "leaderboard.json";
+const LEADERBOARD_LEN: usize = 10; // best runs kept
+const NAME_MAX_CHARS: usize = 12;
+
+/// One finished run on the local leaderboard. Field names match the game's
+/// `leaderboard::LeaderboardEntry`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct LeaderboardEntry {
+    name: String,
+    score: f32,
+    seed: u64,
+    duration_secs: f32,
+    /// Seconds since the Unix epoch, stamped when the score is submitted.
+    date: u64,
+}
+
+/// What the game sends; the date is filled in here.
+#[derive(Deserialize, Debug)]
+struct ScoreSubmission {
+    name: String,
+    score: f32,
+    seed: u64,
+    duration_secs: f32,
+}
+
+/// Save slots are JSON files in the app data directory. Keys are restricted
+/// to a plain file stem so they can't point outside it.
+fn save_path(app: &tauri::AppHandle, key: &str) -> Result<PathBuf, String> {
+    let valid = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        return Err(format!("invalid save key {key:?}"));
+    }
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(format!("{key}.json")))
+}
+
+#[tauri::command]
+fn load_save(app: tauri::AppHandle, key: String) -> Result<Option<String>, String> {
+    let path = save_path(&app, &key)?;
+    match fs::read_to_string(&path) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Write then rename so a crash mid-write can't leave a truncated file.
+fn write_atomic(path: &Path, data: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, data).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn store_save(app: tauri::AppHandle, key: String, data: String) -> Result<(), String> {
+    let path = save_path(&app, &key)?;
+    write_atomic(&path, &data)
+}
+
+fn leaderboard_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(LEADERBOARD_FILE))
+}
+
+/// A missing file is an empty board. One that can't be read or parsed is an
+/// error, so a submission never writes over runs it couldn't see.
+fn read_leaderboard(path: &Path) -> Result<Vec<LeaderboardEntry>, String> {
+    match fs::read_to_string(path) {
+        Ok(data) => {
+            serde_json::from_str(&data).map_err(|e| format!("unreadable {}: {e}", path.display()))
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Adds `entry` to the board at `path` and writes it back.
+fn add_to_leaderboard(
+    path: &Path,
+    entry: LeaderboardEntry,
+) -> Result<Vec<LeaderboardEntry>, String> {
+    let mut entries = read_leaderboard(path)?;
+    entries.push(entry);
+    rank(&mut entries);
+    let data = serde_json::to_string(&entries).map_err(|e| e.to_string())?;
+    write_atomic(path, &data)?;
+    Ok(entries)
+}
+
+/// Highest score first; ties go to the earlier run.
+fn rank(entries: &mut Vec<LeaderboardEntry>) {
+    entries.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.date.cmp(&b.date)));
+    entries.truncate(LEADERBOARD_LEN);
+}
+
+fn clean_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(NAME_MAX_CHARS)
+        .collect();
+    if name.is_empty() {
+        "PLAYER".to_string()
+    } else {
+        name
+    }
+}
+
+/// Records a finished run and returns the updated board.
 #[tauri::command]
-fn greet(name: &str) -> String {
-    format!("Hello, {}! You've been greeted from Rust!", name)
+fn submit_score(
+    app: tauri::AppHandle,
+    entry: ScoreSubmission,
+) -> Result<Vec<LeaderboardEntry>, String> {
+    let path = leaderboard_path(&app)?;
+    let date = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    add_to_leaderboard(
+        &path,
+        LeaderboardEntry {
+            name: clean_name(&entry.name),
+            score: entry.score,
+            seed: entry.seed,
+            duration_secs: entry.duration_secs,
+            date,
+        },
+    )
+}
+
+#[tauri::command]
+fn get_leaderboard(app: tauri::AppHandle) -> Result<Vec<LeaderboardEntry>, String> {
+    let path = leaderboard_path(&app)?;
+    read_leaderboard(&path)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![greet])
+        .plugin(motion::init())
+        .invoke_handler(tauri::generate_handler![
+            load_save,
+            store_save,
+            submit_score,
+            get_leaderboard,
+            motion::read_tilt
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: f32) -> LeaderboardEntry {
+        LeaderboardEntry {
+            name: "PLAYER".to_string(),
+            score,
+            seed: 1,
+            duration_secs: 10.0,
+            date: 0,
+        }
+    }
+
+    /// A fresh leaderboard path under the temp dir.
+    fn board_path(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("taurc-{test}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join(LEADERBOARD_FILE)
+    }
+
+    #[test]
+    fn missing_board_starts_empty() {
+        let path = board_path("missing");
+        assert!(read_leaderboard(&path).unwrap().is_empty());
+        let entries = add_to_leaderboard(&path, entry(5.0)).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(read_leaderboard(&path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn malformed_board_is_left_intact() {
+        let path = board_path("malformed");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let malformed = r#"[{"name": "PLAYER", "score": 900.0"#;
+        fs::write(&path, malformed).unwrap();
+        assert!(read_leaderboard(&path).is_err());
+        assert!(add_to_leaderboard(&path, entry(5.0)).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), malformed);
+    }
+}
@@ -0,0 +1,61 @@
+//! Tilt readings for the game's tilt steering.
+//!
+//! On Android, `MotionPlugin.kt` in the generated Android project listens to
+//! the gravity sensor and keeps the latest roll; `read_tilt` fetches it.
+//! Other platforms have no sensor, so `read_tilt` always answers `None`.
+
+use serde::{Deserialize, Serialize};
+use tauri::plugin::{Builder, TauriPlugin};
+
+#[cfg(target_os = "android")]
+use tauri::Manager;
+
+/// One reading. Field names match the game's `motion::Tilt`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct Tilt {
+    /// Radians; positive when the right side of the screen dips.
+    roll: f32,
+}
+
+#[cfg(target_os = "android")]
+struct MotionPlugin(tauri::plugin::PluginHandle<tauri::Wry>);
+
+/// What the Kotlin side resolves with; no `roll` until the sensor reports.
+#[cfg(target_os = "android")]
+#[derive(Deserialize)]
+struct Reading {
+    roll: Option<f32>,
+}
+
+pub fn init() -> TauriPlugin<tauri::Wry> {
+    Builder::new("motion")
+        .setup(|app, api| {
+            #[cfg(target_os = "android")]
+            {
+                let handle = api.register_android_plugin("nandi.speedblock", "MotionPlugin")?;
+                app.manage(MotionPlugin(handle));
+            }
+            #[cfg(not(target_os = "android"))]
+            let _ = (app, api);
+            Ok(())
+        })
+        .build()
+}
+
+#[tauri::command]
+pub fn read_tilt(app: tauri::AppHandle) -> Result<Option<Tilt>, String> {
+    #[cfg(target_os = "android")]
+    {
+        let plugin = app.state::<MotionPlugin>();
+        let reading: Reading = plugin
+            .0
+            .run_mobile_plugin("readTilt", ())
+            .map_err(|e| e.to_string())?;
+        Ok(reading.roll.map(|roll| Tilt { roll }))
+    }
+    #[cfg(not(target_os = "android"))]
+    {
+        let _ = app;
+        Ok(None)
+    }
+}
@@ -0,0 +1,126 @@
+//! Coins: pickups laid out in hop-shaped arcs in the gaps between waves.
+//!
+//! Each arc rises to the top of a jump, so the high coins need one. Coins
+//! that would sit inside an obstacle are left out. A live run's coins are
+//! added to the wallet when it ends, and the wallet is saved with the
+//! profile.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::collision::{hit_half_extents, swept_overlap};
+use crate::game::{Obstacle, ObstacleKind, Player, RunRng, Stance, OBSTACLE_START_Z, TRACK_HALF_X};
+use crate::interpolation::SimPosition;
+use crate::lanes::ControlScheme;
+
+pub const COIN_SIZE: Vec3 = Vec3::new(0.4, 0.4, 0.1);
+pub const COIN_CENTRE_Y: f32 = 0.4; // lowest coins sit at a runner's height
+pub const COIN_ARC_INTERVAL_SECS: f32 = 3.0; // between arcs
+pub const COIN_ARC_LEN: usize = 7; // coins per arc
+pub const COIN_SPACING: f32 = 1.5; // depth between coins in an arc
+pub const COIN_ARC_HEIGHT: f32 = 1.0; // rise at the top of an arc; within a jump
+pub const COIN_ARC_LEAD: f32 = 2.0; // first coin's distance in front of a new wave
+
+#[derive(Component)]
+pub struct Coin;
+
+/// Coins picked up this run, and the saved balance.
+#[derive(Resource, Default)]
+pub struct Coins {
+    pub run: u32,
+    pub wallet: u64,
+}
+
+#[derive(Resource)]
+pub struct CoinTimer(pub Timer);
+
+impl Default for CoinTimer {
+    fn default() -> Self {
+        CoinTimer(Timer::from_seconds(
+            COIN_ARC_INTERVAL_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+/// Positions of an arc at `x`, nearest the player last, starting
+/// [`COIN_ARC_LEAD`] in front of the spawn line.
+pub fn arc(x: f32) -> Vec<Vec3> {
+    (0..COIN_ARC_LEN)
+        .map(|i| {
+            let t = i as f32 / (COIN_ARC_LEN - 1) as f32;
+            let y = COIN_CENTRE_Y + COIN_ARC_HEIGHT * (t * std::f32::consts::PI).sin();
+            let z = OBSTACLE_START_Z + COIN_ARC_LEAD + (COIN_ARC_LEN - 1 - i) as f32 * COIN_SPACING;
+            Vec3::new(x, y, z)
+        })
+        .collect()
+}
+
+pub(crate) fn reset_coins(mut coins: ResMut<Coins>, mut timer: ResMut<CoinTimer>) {
+    coins.run = 0;
+    timer.0.reset();
+}
+
+/// Lays out an arc every [`COIN_ARC_INTERVAL_SECS`] just in front of the
+/// newest wave. Everything on the track moves together, so a coin clear of
+/// the obstacles now stays clear.
+pub(crate) fn spawn_coins(
+    mut commands: Commands,
+    time: Res<Time>,
+    scheme: Res<ControlScheme>,
+    mut timer: ResMut<CoinTimer>,
+    mut rng: ResMut<RunRng>,
+    q_obstacles: Query<(&SimPosition, &ObstacleKind), With<Obstacle>>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let x = match scheme.lanes() {
+        Some(lanes) => lanes.centre(rng.0.gen_range(0..lanes.count())),
+        None => rng.0.gen_range(-TRACK_HALF_X..=TRACK_HALF_X),
+    };
+    for at in arc(x) {
+        let coin = SimPosition::new(at);
+        let blocked = q_obstacles
+            .iter()
+            .any(|(op, kind)| swept_overlap(&coin, op, hit_half_extents(COIN_SIZE, kind.size())));
+        if blocked {
+            continue;
+        }
+        // Mesh and material are attached by the view plugin.
+        commands.spawn((
+            SpatialBundle::from_transform(Transform::from_translation(at)),
+            coin,
+            Coin,
+        ));
+    }
+}
+
+pub(crate) fn collect_coins(
+    mut commands: Commands,
+    mut coins: ResMut<Coins>,
+    q_player: Query<(&SimPosition, &Stance), With<Player>>,
+    q: Query<(Entity, &SimPosition), With<Coin>>,
+) {
+    let Ok((player, stance)) = q_player.get_single() else {
+        return;
+    };
+    let half = hit_half_extents(stance.size(), COIN_SIZE);
+    for (e, pos) in &q {
+        if swept_overlap(player, pos, half) {
+            coins.run += 1;
+            commands.entity(e).despawn();
+        }
+    }
+}
+
+/// Adds the live run's coins to the wallet; `save_profile` runs after it.
+pub(crate) fn bank_coins(mut coins: ResMut<Coins>) {
+    coins.wallet += u64::from(coins.run);
+}
+
+pub(crate) fn clear_coins(mut commands: Commands, q: Query<Entity, With<Coin>>) {
+    for e in &q {
+        commands.entity(e).despawn_recursive();
+    }
+}
@@ -0,0 +1,107 @@
+//! Near misses: passing an obstacle within [`NEAR_MISS_MARGIN`] without
+//! touching it scores bonus points and builds a combo multiplier.
+//!
+//! An obstacle is marked [`Grazed`] the first tick the player is inside its
+//! near-miss zone, and pays out once it has fully passed, so a graze that
+//! turns into a hit never counts. The multiplier holds for a moment after
+//! each near miss, then decays back to 1.
+
+use bevy::prelude::*;
+
+use crate::collision::{hit_half_extents, swept_overlap};
+use crate::game::{Obstacle, ObstacleKind, Player, Score, Stance, PLAYER_Z};
+use crate::interpolation::SimPosition;
+use crate::powerups::ActiveEffects;
+
+pub const NEAR_MISS_MARGIN: f32 = 0.3; // extra reach around each obstacle's hit box
+pub const NEAR_MISS_POINTS: f32 = 25.0; // before the multiplier
+pub const COMBO_STEP: f32 = 0.5; // multiplier gained per near miss
+pub const COMBO_MAX: f32 = 5.0;
+pub const COMBO_HOLD_SECS: f32 = 1.5; // multiplier holds this long after a near miss
+pub const COMBO_DECAY_PER_SEC: f32 = 1.0; // then falls back towards 1 at this rate
+
+/// Set on an obstacle once the player has been inside its near-miss zone.
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+pub struct Grazed;
+
+/// This run's near misses and current multiplier.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct Combo {
+    pub multiplier: f32,
+    /// Seconds since the last near miss.
+    pub since_last: f32,
+    pub near_misses: u32,
+    /// Points from near misses, already included in [`Score::value`].
+    pub bonus: f32,
+    pub best_multiplier: f32,
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Combo {
+            multiplier: 1.0,
+            since_last: 0.0,
+            near_misses: 0,
+            bonus: 0.0,
+            best_multiplier: 1.0,
+        }
+    }
+}
+
+impl Combo {
+    /// Points for one near miss at the current multiplier; raises it.
+    pub fn near_miss(&mut self) -> f32 {
+        let points = NEAR_MISS_POINTS * self.multiplier;
+        self.multiplier = (self.multiplier + COMBO_STEP).min(COMBO_MAX);
+        self.best_multiplier = self.best_multiplier.max(self.multiplier);
+        self.since_last = 0.0;
+        self.near_misses += 1;
+        points
+    }
+
+    pub fn decay(&mut self, dt: f32) {
+        self.since_last += dt;
+        if self.since_last > COMBO_HOLD_SECS {
+            self.multiplier = (self.multiplier - COMBO_DECAY_PER_SEC * dt).max(1.0);
+        }
+    }
+}
+
+pub(crate) fn reset_combo(mut combo: ResMut<Combo>) {
+    *combo = Combo::default();
+}
+
+/// Marks obstacles the player grazed this tick and pays out for grazed ones
+/// that have now fully passed. Runs after `collision_system`, so anything
+/// still here never hit.
+pub(crate) fn near_miss_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    effects: Res<ActiveEffects>,
+    mut combo: ResMut<Combo>,
+    mut score: ResMut<Score>,
+    q_player: Query<(&SimPosition, &Stance), With<Player>>,
+    q_obstacles: Query<(Entity, &SimPosition, &ObstacleKind, Has<Grazed>), With<Obstacle>>,
+) {
+    combo.decay(time.delta_seconds());
+    let Ok((player, stance)) = q_player.get_single() else {
+        return;
+    };
+    for (e, op, kind, grazed) in &q_obstacles {
+        let half = hit_half_extents(stance.size(), kind.size());
+        let zone = half + Vec3::new(NEAR_MISS_MARGIN, NEAR_MISS_MARGIN, 0.0);
+        if !grazed && !swept_overlap(player, op, zone) {
+            continue;
+        }
+        // A fast obstacle can graze and pass in the same tick.
+        let passed = op.previous.z <= PLAYER_Z + half.z && op.current.z > PLAYER_Z + half.z;
+        if passed {
+            let points = combo.near_miss() * effects.score_factor();
+            combo.bonus += points;
+            score.value += points;
+        } else if !grazed {
+            commands.entity(e).insert(Grazed);
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Which device is steering, and how far each one moves the player.
+//!
+//! The last device actually used wins: a key press, a pad button or stick,
+//! a touch, or a mouse that moved or clicked. A cursor resting inside the
+//! window doesn't count, so it no longer cancels keyboard steps. The mouse
+//! either places the player under the cursor or, in relative mode, nudges
+//! it by how far it moved with the cursor locked. Sensitivities are tuned
+//! on the menu, one device at a time, and saved with the mouse mode in the
+//! profile.
+
+use bevy::input::gamepad::GamepadAxisChangedEvent;
+use bevy::input::mouse::MouseMotion;
+use bevy::prelude::*;
+use bevy::window::{CursorGrabMode, PrimaryWindow};
+use serde::{Deserialize, Serialize};
+
+use crate::game::GameState;
+use crate::pause::PauseState;
+
+pub const DEFAULT_DRAG_X_PER_PX: f32 = 0.02; // world units per horizontal pixel of mouse or touch
+pub const MIN_SENSITIVITY: f32 = 0.1; // as a multiple of the default
+pub const MAX_SENSITIVITY: f32 = 5.0;
+pub const SENSITIVITY_STEP: f32 = 0.25; // per menu press, as a multiple of the default
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    Keyboard,
+    Mouse,
+    Touch,
+    Gamepad,
+}
+
+/// The device used most recently, if any has been.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ActiveDevice(pub Option<InputDevice>);
+
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MouseMode {
+    /// The player follows the cursor across the window.
+    #[default]
+    Absolute,
+    /// Mouse movement nudges the player; the cursor is locked during a run.
+    Relative,
+}
+
+impl MouseMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            MouseMode::Absolute => "Absolute",
+            MouseMode::Relative => "Relative",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            MouseMode::Absolute => MouseMode::Relative,
+            MouseMode::Relative => MouseMode::Absolute,
+        }
+    }
+}
+
+/// How far each device moves the player. Mouse and touch are world units per
+/// pixel; the stick scales its full tilt, 1.0 reaching the track's edge.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct Sensitivity {
+    pub mouse: f32,
+    pub touch: f32,
+    pub stick: f32,
+}
+
+impl Default for Sensitivity {
+    fn default() -> Self {
+        Sensitivity {
+            mouse: DEFAULT_DRAG_X_PER_PX,
+            touch: DEFAULT_DRAG_X_PER_PX,
+            stick: 1.0,
+        }
+    }
+}
+
+/// The device whose sensitivity the menu is tuning.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SensitivityDevice {
+    #[default]
+    Mouse,
+    Touch,
+    Stick,
+}
+
+impl SensitivityDevice {
+    pub fn label(self) -> &'static str {
+        match self {
+            SensitivityDevice::Mouse => "Mouse",
+            SensitivityDevice::Touch => "Touch",
+            SensitivityDevice::Stick => "Stick",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            SensitivityDevice::Mouse => SensitivityDevice::Touch,
+            SensitivityDevice::Touch => SensitivityDevice::Stick,
+            SensitivityDevice::Stick => SensitivityDevice::Mouse,
+        }
+    }
+}
+
+impl Sensitivity {
+    fn setting(&mut self, device: SensitivityDevice) -> &mut f32 {
+        match device {
+            SensitivityDevice::Mouse => &mut self.mouse,
+            SensitivityDevice::Touch => &mut self.touch,
+            SensitivityDevice::Stick => &mut self.stick,
+        }
+    }
+
+    fn get(self, device: SensitivityDevice) -> f32 {
+        match device {
+            SensitivityDevice::Mouse => self.mouse,
+            SensitivityDevice::Touch => self.touch,
+            SensitivityDevice::Stick => self.stick,
+        }
+    }
+
+    /// `device`'s setting as a multiple of its default.
+    pub fn multiple(self, device: SensitivityDevice) -> f32 {
+        self.get(device) / Sensitivity::default().get(device)
+    }
+
+    /// Moves `device`'s setting `steps` [`SENSITIVITY_STEP`]s up (or down,
+    /// if negative), staying within the bounds [`Sensitivity::clamped`]
+    /// keeps.
+    pub fn adjust(&mut self, device: SensitivityDevice, steps: i32) {
+        let multiple =
+            ((self.multiple(device) / SENSITIVITY_STEP).round() + steps as f32) * SENSITIVITY_STEP;
+        let multiple = multiple.clamp(MIN_SENSITIVITY, MAX_SENSITIVITY);
+        *self.setting(device) = multiple * Sensitivity::default().get(device);
+    }
+
+    /// Keeps every setting within [`MIN_SENSITIVITY`]..=[`MAX_SENSITIVITY`]
+    /// times its default, so a bad save can't leave a device dead.
+    pub fn clamped(self) -> Self {
+        let default = Sensitivity::default();
+        let clamp = |value: f32, default: f32| {
+            if value.is_finite() {
+                value.clamp(default * MIN_SENSITIVITY, default * MAX_SENSITIVITY)
+            } else {
+                default
+            }
+        };
+        Sensitivity {
+            mouse: clamp(self.mouse, default.mouse),
+            touch: clamp(self.touch, default.touch),
+            stick: clamp(self.stick, default.stick),
+        }
+    }
+}
+
+/// Notes the device used this frame. If several were, keys and pad buttons
+/// beat the pointers, so a mouse nudged while pressing keys doesn't steal.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn track_active_device(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut motion_evs: EventReader<MouseMotion>,
+    touches: Res<Touches>,
+    pad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut axis_evs: EventReader<GamepadAxisChangedEvent>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut last_cursor: Local<Option<Vec2>>,
+    mut active: ResMut<ActiveDevice>,
+) {
+    let cursor = windows.get_single().ok().and_then(Window::cursor_position);
+    let cursor_moved = cursor.is_some() && cursor != *last_cursor;
+    *last_cursor = cursor;
+    let motion = motion_evs.read().any(|ev| ev.delta != Vec2::ZERO);
+
+    let mut used = None;
+    if cursor_moved || motion || mouse.get_just_pressed().next().is_some() {
+        used = Some(InputDevice::Mouse);
+    }
+    if touches.any_just_pressed() {
+        used = Some(InputDevice::Touch);
+    }
+    if axis_evs.read().count() > 0 || pad_buttons.get_just_pressed().next().is_some() {
+        used = Some(InputDevice::Gamepad);
+    }
+    if keys.get_just_pressed().next().is_some() {
+        used = Some(InputDevice::Keyboard);
+    }
+    if used.is_some() && used != active.0 {
+        debug!("[input] steering with {used:?}");
+        active.0 = used;
+    }
+}
+
+/// M on the menu switches the mouse mode.
+pub(crate) fn menu_cycle_mouse_mode(keys: Res<ButtonInput<KeyCode>>, mut mode: ResMut<MouseMode>) {
+    if keys.just_pressed(KeyCode::KeyM) {
+        *mode = mode.next();
+    }
+}
+
+/// S on the menu picks the device to tune; minus and plus (or `=`) lower
+/// and raise its sensitivity.
+pub(crate) fn menu_adjust_sensitivity(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut device: ResMut<SensitivityDevice>,
+    mut sensitivity: ResMut<Sensitivity>,
+) {
+    if keys.just_pressed(KeyCode::KeyS) {
+        *device = device.next();
+    }
+    if keys.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        sensitivity.adjust(*device, -1);
+    }
+    if keys.any_just_pressed([KeyCode::Equal, KeyCode::NumpadAdd]) {
+        sensitivity.adjust(*device, 1);
+    }
+}
+
+/// Locks and hides the cursor while a run is being played in relative mode,
+/// and gives it back otherwise.
+pub(crate) fn grab_cursor(
+    mode: Res<MouseMode>,
+    state: Res<State<GameState>>,
+    pause: Option<Res<State<PauseState>>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let running = pause.is_some_and(|p| *p.get() == PauseState::Running);
+    let grab = *mode == MouseMode::Relative && *state.get() == GameState::Playing && running;
+    let grab_mode = if grab {
+        CursorGrabMode::Locked
+    } else {
+        CursorGrabMode::None
+    };
+    for mut window in &mut windows {
+        if window.cursor.grab_mode != grab_mode {
+            window.cursor.grab_mode = grab_mode;
+            window.cursor.visible = !grab;
+        }
+    }
+}
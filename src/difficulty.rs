@@ -0,0 +1,186 @@
+//! Difficulty presets loaded from `assets/difficulty/*.difficulty.ron`.
+//!
+//! Each preset is a [`DifficultyProfile`]: the speed and spawn curves the run
+//! systems read, so they can be tuned without recompiling. The preset picked
+//! on the menu is copied into the [`DifficultyProfile`] resource when a run
+//! starts. A preset that fails to load falls back to the built-in Normal
+//! curve from the constants in [`crate::game`].
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext, LoadState};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    OBSTACLE_SPEED, OBSTACLE_SPEED_GROWTH_PER_SEC, PLAYER_LERP_SPEED, SPAWN_INTERVAL_BASE,
+    SPAWN_INTERVAL_DECAY_PER_SEC, SPAWN_INTERVAL_MIN,
+};
+use crate::ghost::BestGhost;
+
+/// Tuning for one difficulty. Also the resource holding the active run's
+/// curve.
+#[derive(Asset, Resource, TypePath, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DifficultyProfile {
+    /// Units/sec towards the camera at the start of a run.
+    pub obstacle_speed: f32,
+    /// Speed gained per second survived.
+    pub obstacle_speed_growth_per_sec: f32,
+    /// Seconds between spawns at the start of a run.
+    pub spawn_interval_base: f32,
+    /// Lower bound on the spawn interval.
+    pub spawn_interval_min: f32,
+    /// How much the spawn interval shortens per second survived.
+    pub spawn_interval_decay_per_sec: f32,
+    /// X-axis smoothing towards the player's target.
+    pub player_lerp_speed: f32,
+    /// Wave pattern tier a run starts at; see [`crate::waves`].
+    #[serde(default)]
+    pub start_tier: u32,
+}
+
+impl Default for DifficultyProfile {
+    fn default() -> Self {
+        DifficultyProfile {
+            obstacle_speed: OBSTACLE_SPEED,
+            obstacle_speed_growth_per_sec: OBSTACLE_SPEED_GROWTH_PER_SEC,
+            spawn_interval_base: SPAWN_INTERVAL_BASE,
+            spawn_interval_min: SPAWN_INTERVAL_MIN,
+            spawn_interval_decay_per_sec: SPAWN_INTERVAL_DECAY_PER_SEC,
+            player_lerp_speed: PLAYER_LERP_SPEED,
+            start_tier: 0,
+        }
+    }
+}
+
+/// The preset picked on the menu. Saved with the profile.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn asset_path(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "difficulty/easy.difficulty.ron",
+            Difficulty::Normal => "difficulty/normal.difficulty.ron",
+            Difficulty::Hard => "difficulty/hard.difficulty.ron",
+        }
+    }
+
+    /// The next preset, wrapping around; the menu cycles with this.
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DifficultyLoader;
+
+impl AssetLoader for DifficultyLoader {
+    type Asset = DifficultyProfile;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<DifficultyProfile, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["difficulty.ron"]
+    }
+}
+
+/// Handles to every preset, in [`Difficulty::ALL`] order. Held for the whole
+/// session so the presets stay loaded.
+#[derive(Resource, Default)]
+pub(crate) struct DifficultyPresets(Vec<Handle<DifficultyProfile>>);
+
+impl DifficultyPresets {
+    fn handle(&self, difficulty: Difficulty) -> Option<&Handle<DifficultyProfile>> {
+        let i = Difficulty::ALL.iter().position(|d| *d == difficulty)?;
+        self.0.get(i)
+    }
+}
+
+pub(crate) fn start_difficulty_load(
+    asset_server: Res<AssetServer>,
+    mut presets: ResMut<DifficultyPresets>,
+) {
+    presets.0 = Difficulty::ALL
+        .iter()
+        .map(|d| asset_server.load(d.asset_path()))
+        .collect();
+}
+
+/// True once every preset has either loaded or failed.
+pub(crate) fn difficulty_presets_settled(
+    asset_server: &AssetServer,
+    presets: &DifficultyPresets,
+) -> bool {
+    presets.0.iter().all(|handle| {
+        matches!(
+            asset_server.load_state(handle),
+            LoadState::Loaded | LoadState::Failed(_)
+        )
+    })
+}
+
+/// Copies the chosen preset into the run's [`DifficultyProfile`].
+pub(crate) fn apply_difficulty(
+    difficulty: Res<Difficulty>,
+    presets: Res<DifficultyPresets>,
+    assets: Res<Assets<DifficultyProfile>>,
+    mut active: ResMut<DifficultyProfile>,
+) {
+    let loaded = presets.handle(*difficulty).and_then(|h| assets.get(h));
+    *active = match loaded {
+        Some(profile) => profile.clone(),
+        None => {
+            warn!(
+                "[difficulty] {} preset unavailable, using built-in curve",
+                difficulty.label()
+            );
+            DifficultyProfile::default()
+        }
+    };
+}
+
+/// D on the menu cycles the preset.
+pub(crate) fn menu_cycle_difficulty(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    if keys.just_pressed(KeyCode::KeyD) {
+        *difficulty = difficulty.next();
+    }
+}
+
+/// The ghost raced a different curve, so it is dropped when the preset
+/// changes.
+pub(crate) fn drop_stale_ghost(mut ghost: ResMut<BestGhost>) {
+    ghost.0 = None;
+}
@@ -0,0 +1,326 @@
+//! Reachability checks that keep every spawned sequence survivable.
+//!
+//! [`Reach`] is the set of X positions a player moving at (slightly under)
+//! top lateral speed could occupy right now without having been hit. It is
+//! advanced every tick against the real obstacles, independent of where the
+//! actual player is, so replays see the same decisions. Before a wave is
+//! spawned, a [`Lookahead`] plays the track forward with the wave added; if
+//! the reachable set would run out, the wave is shifted, re-rolled or dropped.
+//!
+//! Only blocks close off X. Hurdles and barriers span the track and are
+//! cleared by a jump or slide instead, so the check there is timing: each
+//! pass must be short enough for one action, of one kind, and far enough
+//! from the previous pass to land or stand up in between.
+//!
+//! In lane mode a block closes off its whole lane, and waves shift by whole
+//! lanes so they stay on lane centres.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::collision::hit_half_extents;
+use crate::difficulty::DifficultyProfile;
+use crate::game::{
+    Obstacle, ObstacleKind, JUMP_SECS, PLAYER_SIZE, PLAYER_Z, SLIDE_SECS, TRACK_HALF_X,
+};
+use crate::interpolation::SimPosition;
+use crate::lanes::{ControlScheme, Lanes};
+use crate::waves;
+
+pub const LATERAL_SLACK: f32 = 0.95; // plan at this fraction of top lateral speed
+pub const X_MARGIN: f32 = 0.05; // clearance kept beside every obstacle
+pub const Z_MARGIN: f32 = 0.05; // lookahead treats obstacles as this much deeper
+pub const FAIR_ATTEMPTS: usize = 4; // fresh waves tried before a spawn is dropped
+pub const SHIFT_STEP: f32 = 0.5; // sideways nudge tried on an unfair wave
+pub const VERTICAL_PASS_MAX_SECS: f32 = 0.25; // longest stretch one jump or slide covers
+pub const ACTION_GAP_SECS: f32 = 0.2; // spare time between two jumps or slides
+const MAX_LOOKAHEAD_TICKS: u32 = 60 * 20;
+
+/// X positions still reachable, as sorted, disjoint closed intervals, plus
+/// the timing of the last hurdle or barrier pass.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct Reach {
+    intervals: Vec<(f32, f32)>,
+    /// Kind of the hurdles or barriers passing the player right now.
+    passing: Option<ObstacleKind>,
+    /// Seconds since the latest such pass began.
+    since_pass: Option<f32>,
+}
+
+impl Default for Reach {
+    /// Where every run starts: the player on the centre line.
+    fn default() -> Self {
+        Reach::at(0.0)
+    }
+}
+
+impl Reach {
+    pub fn at(x: f32) -> Self {
+        Reach {
+            intervals: vec![(x, x)],
+            passing: None,
+            since_pass: None,
+        }
+    }
+
+    pub fn intervals(&self) -> &[(f32, f32)] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// One tick: move up to `max_step` either way without passing through a
+    /// `blocked` band (open intervals), and without starting or ending in one.
+    pub fn step(&mut self, max_step: f32, blocked: &[(f32, f32)]) {
+        let mut bands = blocked.to_vec();
+        bands.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut next = Vec::new();
+        for &(a, b) in &self.intervals {
+            for (a, b) in subtract(a, b, &bands) {
+                let floor = bands
+                    .iter()
+                    .filter(|band| band.1 <= a)
+                    .fold(-TRACK_HALF_X, |m, band| m.max(band.1));
+                let ceil = bands
+                    .iter()
+                    .filter(|band| band.0 >= b)
+                    .fold(TRACK_HALF_X, |m, band| m.min(band.0));
+                next.push(((a - max_step).max(floor), (b + max_step).min(ceil)));
+            }
+        }
+        self.intervals = merge(next);
+    }
+
+    /// One tick of hurdle and barrier timing, given the kinds of those
+    /// passing the player. False if no jump or slide could clear them.
+    pub fn note_vertical(&mut self, dt: f32, passing: &[ObstacleKind]) -> bool {
+        if let Some(since) = &mut self.since_pass {
+            *since += dt;
+        }
+        let Some(&kind) = passing.first() else {
+            self.passing = None;
+            return true;
+        };
+        if passing.iter().any(|k| *k != kind) {
+            return false;
+        }
+        match self.passing {
+            Some(current) => {
+                current == kind && self.since_pass.unwrap_or(0.0) <= VERTICAL_PASS_MAX_SECS
+            }
+            None => {
+                let recovered = self
+                    .since_pass
+                    .is_none_or(|since| since >= JUMP_SECS.max(SLIDE_SECS) + ACTION_GAP_SECS);
+                self.passing = Some(kind);
+                self.since_pass = Some(0.0);
+                recovered
+            }
+        }
+    }
+}
+
+/// Parts of `[a, b]` outside every open band.
+fn subtract(a: f32, b: f32, bands: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let mut parts = vec![(a, b)];
+    for &(lo, hi) in bands {
+        parts = parts
+            .into_iter()
+            .flat_map(|(a, b)| {
+                if hi <= a || lo >= b {
+                    return vec![(a, b)];
+                }
+                let mut kept = Vec::new();
+                if a <= lo {
+                    kept.push((a, lo));
+                }
+                if hi <= b {
+                    kept.push((hi, b));
+                }
+                kept
+            })
+            .collect();
+    }
+    parts
+}
+
+fn merge(mut intervals: Vec<(f32, f32)>) -> Vec<(f32, f32)> {
+    intervals.retain(|(a, b)| a <= b);
+    intervals.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut merged: Vec<(f32, f32)> = Vec::with_capacity(intervals.len());
+    for (a, b) in intervals {
+        match merged.last_mut() {
+            Some(last) if a <= last.1 => last.1 = last.1.max(b),
+            _ => merged.push((a, b)),
+        }
+    }
+    merged
+}
+
+/// X band the player must stay out of while a block at `x` passes; with
+/// `lanes`, at least the block's whole lane.
+pub fn blocked_band(x: f32, lanes: Option<Lanes>) -> (f32, f32) {
+    let hit = hit_half_extents(PLAYER_SIZE, ObstacleKind::Block.size()).x;
+    let reach = lanes.map_or(hit, |l| hit.max(l.width() / 2.0)) + X_MARGIN;
+    (x - reach, x + reach)
+}
+
+/// Whether an obstacle moving from `z0` to `z1` this tick passes the player.
+fn sweeps_player(kind: ObstacleKind, z0: f32, z1: f32, z_pad: f32) -> bool {
+    let half_z = hit_half_extents(PLAYER_SIZE, kind.size()).z + z_pad;
+    z1 > PLAYER_Z - half_z && z0 < PLAYER_Z + half_z
+}
+
+/// Lateral bands and vertical kinds for the obstacles passing this tick.
+fn hazards(
+    passing: impl Iterator<Item = (f32, ObstacleKind)>,
+    lanes: Option<Lanes>,
+) -> (Vec<(f32, f32)>, Vec<ObstacleKind>) {
+    let mut bands = Vec::new();
+    let mut vertical = Vec::new();
+    for (x, kind) in passing {
+        match kind {
+            ObstacleKind::Block => bands.push(blocked_band(x, lanes)),
+            _ => vertical.push(kind),
+        }
+    }
+    (bands, vertical)
+}
+
+/// Plays the track forward tick by tick, the way the fixed simulation will.
+pub struct Lookahead {
+    /// Obstacle speed on the next tick.
+    pub speed: f32,
+    /// Speed gained per second survived.
+    pub accel: f32,
+    /// The player's top lateral speed, before [`LATERAL_SLACK`].
+    pub lateral_speed: f32,
+    /// Set in lane mode.
+    pub lanes: Option<Lanes>,
+    pub dt: f32,
+}
+
+impl Lookahead {
+    pub fn new(profile: &DifficultyProfile, lanes: Option<Lanes>, speed: f32, dt: f32) -> Self {
+        Lookahead {
+            speed,
+            accel: profile.obstacle_speed_growth_per_sec,
+            lateral_speed: profile.player_lerp_speed,
+            lanes,
+            dt,
+        }
+    }
+
+    /// Whether some path from `reach` clears every obstacle, given as
+    /// `(x, z)` world positions at the start of the next tick.
+    pub fn survivable(&self, reach: &Reach, obstacles: &[(Vec2, ObstacleKind)]) -> bool {
+        let mut reach = reach.clone();
+        let mut positions = obstacles.to_vec();
+        let mut speed = self.speed;
+        let max_step = self.lateral_speed * LATERAL_SLACK * self.dt;
+        for _ in 0..MAX_LOOKAHEAD_TICKS {
+            positions.retain(|(p, kind)| {
+                p.y < PLAYER_Z + hit_half_extents(PLAYER_SIZE, kind.size()).z + Z_MARGIN
+            });
+            if positions.is_empty() {
+                return true;
+            }
+            let travel = speed * self.dt;
+            let (blocked, vertical) = hazards(
+                positions
+                    .iter()
+                    .filter(|(p, kind)| sweeps_player(*kind, p.y, p.y + travel, Z_MARGIN))
+                    .map(|(p, kind)| (p.x, *kind)),
+                self.lanes,
+            );
+            if !reach.note_vertical(self.dt, &vertical) {
+                return false;
+            }
+            reach.step(max_step, &blocked);
+            if reach.is_empty() {
+                return false;
+            }
+            for (p, _) in &mut positions {
+                p.y += travel;
+            }
+            speed += self.accel * self.dt;
+        }
+        true
+    }
+}
+
+/// Picks a wave whose kind and `(x, depth)` layout pass `fair`, trying
+/// sideways shifts of each candidate before rolling a fresh one. `None`
+/// means this spawn should be skipped. With `lanes`, waves are laid out on
+/// lane centres and shifted a lane at a time.
+pub fn fair_wave(
+    rng: &mut impl Rng,
+    tier: u32,
+    lanes: Option<Lanes>,
+    fair: impl Fn(ObstacleKind, &[Vec2]) -> bool,
+) -> Option<(ObstacleKind, Vec<Vec2>)> {
+    let step = lanes.map_or(SHIFT_STEP, Lanes::width);
+    let max_shift = (2.0 * TRACK_HALF_X / step) as i32;
+    for _ in 0..FAIR_ATTEMPTS {
+        let pattern = waves::pick(rng, tier);
+        let layout = match lanes {
+            Some(lanes) => pattern.lane_layout(rng, lanes),
+            None => pattern.layout(rng),
+        };
+        let shifts = std::iter::once(0).chain((1..=max_shift).flat_map(|i| [i, -i]));
+        for shift in shifts {
+            let dx = shift as f32 * step;
+            let Some(moved) = shifted(&layout, dx) else {
+                continue;
+            };
+            if fair(pattern.kind(), &moved) {
+                return Some((pattern.kind(), moved));
+            }
+        }
+    }
+    None
+}
+
+/// `layout` moved `dx` sideways, if it all stays on the track.
+fn shifted(layout: &[Vec2], dx: f32) -> Option<Vec<Vec2>> {
+    layout
+        .iter()
+        .map(|o| {
+            let x = o.x + dx;
+            (x.abs() <= TRACK_HALF_X).then_some(Vec2::new(x, o.y))
+        })
+        .collect()
+}
+
+pub(crate) fn reset_reach(mut reach: ResMut<Reach>) {
+    *reach = Reach::default();
+}
+
+/// Steps [`Reach`] over the tick the obstacles just moved through. Timing
+/// is only recorded here; the lookahead that let each wave in already
+/// checked it, with a margin.
+pub(crate) fn advance_reach(
+    time: Res<Time>,
+    profile: Res<DifficultyProfile>,
+    scheme: Res<ControlScheme>,
+    mut reach: ResMut<Reach>,
+    q: Query<(&SimPosition, &ObstacleKind), With<Obstacle>>,
+) {
+    let (blocked, vertical) = hazards(
+        q.iter()
+            .filter(|(p, kind)| sweeps_player(**kind, p.previous.z, p.current.z, 0.0))
+            .map(|(p, kind)| (p.current.x, *kind)),
+        scheme.lanes(),
+    );
+    reach.note_vertical(time.delta_seconds(), &vertical);
+    let max_step = profile.player_lerp_speed * LATERAL_SLACK * time.delta_seconds();
+    reach.step(max_step, &blocked);
+    if reach.is_empty() {
+        // Only float drift against the lookahead could get here; keep
+        // spawning rather than rejecting every wave from now on.
+        warn!("[fairness] reachable set ran out; resetting to the whole track");
+        reach.intervals = vec![(-TRACK_HALF_X, TRACK_HALF_X)];
+    }
+}
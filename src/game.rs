@@ -0,0 +1,1070 @@
+use bevy::input::mouse::MouseMotion;
+use bevy::prelude::*;
+use bevy::utils::Instant;
+use bevy::window::{PrimaryWindow, WindowFocused};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::time::Duration;
+
+use crate::coins::{
+    bank_coins, clear_coins, collect_coins, reset_coins, spawn_coins, Coin, CoinTimer, Coins,
+};
+use crate::collision::{hit_half_extents, swept_overlap};
+use crate::combo::{near_miss_system, reset_combo, Combo};
+use crate::devices::{
+    grab_cursor, menu_adjust_sensitivity, menu_cycle_mouse_mode, track_active_device, ActiveDevice,
+    InputDevice, MouseMode, Sensitivity, SensitivityDevice,
+};
+use crate::difficulty::{
+    apply_difficulty, difficulty_presets_settled, drop_stale_ghost, menu_cycle_difficulty,
+    start_difficulty_load, Difficulty, DifficultyLoader, DifficultyPresets, DifficultyProfile,
+};
+use crate::fairness::{advance_reach, fair_wave, reset_reach, Lookahead, Reach};
+use crate::gamepad::{gamepad_input, pad_just_pressed, track_gamepads, ActivePad, CONFIRM_BUTTONS};
+use crate::gestures::{
+    forget_gestures, recognize_gestures, Gesture, GestureTracker, SwipeDirection,
+};
+use crate::ghost::{
+    advance_ghost, despawn_ghost, keep_best_ghost, record_ghost, spawn_ghost, BestGhost,
+    GhostRecorder,
+};
+use crate::input_map::{
+    bindings_input, enter_bindings, menu_open_bindings, Action, InputMap, Rebinding,
+};
+use crate::interpolation::{InterpolationPlugin, SimPosition};
+use crate::lanes::{menu_cycle_controls, ControlScheme, Lanes};
+use crate::leaderboard::{
+    fetch_leaderboard, leaderboard_input, menu_open_leaderboard, poll_leaderboard,
+    submit_finished_run, Leaderboard, LeaderboardClient,
+};
+use crate::motion::{
+    menu_adjust_tilt, menu_toggle_tilt, poll_motion, tilt_input, MotionInput, TiltAngle,
+    TiltSettings, TiltState,
+};
+use crate::pause::{auto_pause, pause_input, resume_time, stop_time, PauseState};
+use crate::pool::{fill_obstacle_pool, ObstaclePool};
+use crate::powerups::{
+    clear_powerups, collect_powerups, pull_powerups, reset_powerups, spawn_powerups, tick_effects,
+    ActiveEffects, PowerUp, PowerUpKind, PowerUpTimer,
+};
+use crate::profile::{
+    finish_profile_load, profile_loaded, record_finished_run, save_profile, start_profile_load,
+    PlayerName, ProfileLoad, RunHistory,
+};
+use crate::replay::{
+    end_replay, feed_replay_input, finish_recording, game_over_watch_replay, record_input,
+    start_recording, start_replay, LastRun, ReplayCursor, RunRecorder,
+};
+use crate::storage::SaveStore;
+use crate::track::{clear_track, scroll_track, spawn_track};
+use crate::waves::{self, WaveCooldown};
+
+// --- Game tuning constants ---
+pub const TRACK_HALF_X: f32 = 4.2; // world units half-width for movement
+pub const PLAYER_Z: f32 = 0.0;
+pub const PLAYER_SIZE: Vec3 = Vec3::new(0.8, 0.8, 0.8);
+pub const OBSTACLE_SIZE: Vec3 = Vec3::new(0.8, 0.8, 0.8);
+pub const HURDLE_SIZE: Vec3 = Vec3::new(1.2, 0.35, 0.4); // low: jump it
+pub const BARRIER_SIZE: Vec3 = Vec3::new(1.2, 1.1, 0.4); // overhead: slide under it
+pub const BARRIER_CLEARANCE: f32 = 0.5; // gap between the ground and a barrier
+pub const OBSTACLE_START_Z: f32 = -25.0;
+pub const OBSTACLE_DESPAWN_Z: f32 = 7.0;
+// Built-in Normal curve. Runs read the `DifficultyProfile` loaded from assets;
+// these are the fallback if a preset is missing.
+pub const OBSTACLE_SPEED: f32 = 20.0; // units/sec towards camera
+pub const OBSTACLE_SPEED_GROWTH_PER_SEC: f32 = 0.50; // incremental speed gain each second survived
+pub const SPAWN_INTERVAL_BASE: f32 = 0.9; // base seconds between spawns
+pub const SPAWN_INTERVAL_MIN: f32 = 0.35; // lower bound on spawn delay
+pub const SPAWN_INTERVAL_DECAY_PER_SEC: f32 = 0.02; // how much to shorten delay per survival second
+pub const PLAYER_LERP_SPEED: f32 = 12.0; // x-axis smoothing towards target
+pub const KEY_STEP_X: f32 = 0.9; // keyboard step per press
+pub const JUMP_SPEED: f32 = 9.0; // upward speed at take-off
+pub const GRAVITY: f32 = 30.0;
+pub const JUMP_SECS: f32 = 2.0 * JUMP_SPEED / GRAVITY; // take-off to landing
+pub const SLIDE_SECS: f32 = 0.6;
+pub const SLIDE_HEIGHT_SCALE: f32 = 0.5; // player height while sliding
+pub const SCORE_PER_SECOND: f32 = 10.0;
+pub const SIM_TICK_HZ: f64 = 60.0; // fixed simulation rate, independent of frame rate
+pub const SEED_MAX_DIGITS: usize = 15; // keeps seeds exact as JS numbers over Tauri IPC
+
+#[derive(States, Default, Debug, Clone, Eq, PartialEq, Hash)]
+pub enum GameState {
+    /// Waiting for the saved profile before showing the menu.
+    #[default]
+    Loading,
+    Menu,
+    Playing,
+    GameOver,
+    /// Re-simulates [`LastRun`] from its recorded input.
+    Replay,
+    /// Best local runs; reached from the menu.
+    Leaderboard,
+    /// Rebinding keys to actions; reached from the menu.
+    Bindings,
+}
+
+/// Present while a run is on screen, live or replayed. Run entities and the
+/// HUD are set up and torn down on this rather than on [`GameState::Playing`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct InRun;
+
+impl ComputedStates for InRun {
+    type SourceStates = GameState;
+
+    fn compute(sources: GameState) -> Option<Self> {
+        matches!(sources, GameState::Playing | GameState::Replay).then_some(InRun)
+    }
+}
+
+#[derive(Component)]
+pub struct Player {
+    pub target_x: f32,
+    /// Jump or slide requested by input, taken by the next tick.
+    pub action: Option<VerticalAction>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VerticalAction {
+    Jump,
+    Slide,
+}
+
+/// The player's vertical state, advanced every tick.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Stance {
+    #[default]
+    Running,
+    /// Seconds since take-off.
+    Jumping(f32),
+    /// Seconds since the slide began.
+    Sliding(f32),
+}
+
+impl Stance {
+    /// Starts `action` if the player is free to: jumps leave the ground or
+    /// cut a slide short; slides need the ground.
+    pub fn start(self, action: VerticalAction) -> Self {
+        match (self, action) {
+            (Stance::Jumping(_), _) => self,
+            (_, VerticalAction::Jump) => Stance::Jumping(0.0),
+            (Stance::Running, VerticalAction::Slide) => Stance::Sliding(0.0),
+            (Stance::Sliding(_), VerticalAction::Slide) => self,
+        }
+    }
+
+    /// One tick later.
+    pub fn advance(self, dt: f32) -> Self {
+        match self {
+            Stance::Running => Stance::Running,
+            Stance::Jumping(t) if t + dt < JUMP_SECS => Stance::Jumping(t + dt),
+            Stance::Sliding(t) if t + dt < SLIDE_SECS => Stance::Sliding(t + dt),
+            _ => Stance::Running,
+        }
+    }
+
+    pub fn size(self) -> Vec3 {
+        match self {
+            Stance::Sliding(_) => PLAYER_SIZE * Vec3::new(1.0, SLIDE_HEIGHT_SCALE, 1.0),
+            _ => PLAYER_SIZE,
+        }
+    }
+
+    /// Height of the player's centre above the ground.
+    pub fn centre_y(self) -> f32 {
+        let lift = match self {
+            Stance::Jumping(t) => JUMP_SPEED * t - 0.5 * GRAVITY * t * t,
+            _ => 0.0,
+        };
+        self.size().y * 0.5 + lift
+    }
+}
+
+/// On every obstacle in play. Pooled obstacles drop it while parked (see
+/// [`crate::pool`]), so it is stored to be cheap to add and remove.
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+pub struct Obstacle;
+
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ObstacleKind {
+    /// Full-height cube: steer around it.
+    #[default]
+    Block,
+    /// Low hurdle: jump it.
+    Hurdle,
+    /// Overhead barrier: slide under it.
+    Barrier,
+}
+
+impl ObstacleKind {
+    pub fn size(self) -> Vec3 {
+        match self {
+            ObstacleKind::Block => OBSTACLE_SIZE,
+            ObstacleKind::Hurdle => HURDLE_SIZE,
+            ObstacleKind::Barrier => BARRIER_SIZE,
+        }
+    }
+
+    /// Height of the obstacle's centre above the ground.
+    pub fn centre_y(self) -> f32 {
+        match self {
+            ObstacleKind::Barrier => BARRIER_CLEARANCE + BARRIER_SIZE.y * 0.5,
+            _ => self.size().y * 0.5,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Score {
+    pub value: f32,
+    pub best: f32,
+}
+
+/// The run's clock. Kept apart from [`Score`], which bonuses can grow, so
+/// only time survived moves the difficulty curve.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RunStats {
+    /// Simulated seconds survived.
+    pub elapsed: f32,
+    /// How far the track has moved past the player, world units.
+    pub distance: f32,
+    /// Obstacle speed this tick, units/sec.
+    pub speed: f32,
+    /// Obstacles that have gone past the player without hitting.
+    pub dodged: u32,
+}
+
+impl RunStats {
+    /// A run that hasn't started yet, at the profile's starting speed.
+    pub fn new(profile: &DifficultyProfile) -> Self {
+        RunStats {
+            speed: obstacle_speed(0.0, profile),
+            ..Default::default()
+        }
+    }
+}
+
+/// Speed obstacles travel towards the player `elapsed` seconds into a run.
+pub fn obstacle_speed(elapsed: f32, profile: &DifficultyProfile) -> f32 {
+    profile.obstacle_speed + elapsed * profile.obstacle_speed_growth_per_sec
+}
+
+#[derive(Resource)]
+pub struct SpawnTimer(pub Timer);
+
+/// Seed for the current (or next) run. Every random decision in a run is
+/// drawn from [`RunRng`], which is reseeded from this on entering Playing.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RunSeed(pub u64);
+
+impl RunSeed {
+    pub fn random() -> Self {
+        RunSeed(rand::thread_rng().gen_range(0..10u64.pow(SEED_MAX_DIGITS as u32)))
+    }
+}
+
+#[derive(Resource)]
+pub struct RunRng(pub ChaCha8Rng);
+
+/// Digits typed on the menu to pick a seed by hand.
+#[derive(Resource, Default)]
+pub struct SeedEntry(pub String);
+
+#[derive(Resource, Default)]
+pub struct OrientationState {
+    pub is_portrait: bool,
+}
+
+#[derive(Resource, Clone, Copy)]
+pub struct AppBootTime {
+    pub app_start: Instant,
+    pub first_update_logged: bool,
+}
+
+impl Default for AppBootTime {
+    fn default() -> Self {
+        AppBootTime {
+            app_start: Instant::now(),
+            first_update_logged: false,
+        }
+    }
+}
+
+/// Gameplay rules and state flow: Loading → Menu → Playing → GameOver.
+///
+/// Needs only states, input, time and assets, so it runs under
+/// `MinimalPlugins` plus `AssetPlugin` (see [`crate::headless`]). Meshes, camera and UI live in
+/// [`crate::view::GameViewPlugin`].
+///
+/// The run itself is simulated in `FixedUpdate` at [`SIM_TICK_HZ`]; input is
+/// read per frame into [`Player::target_x`] and sampled by the next tick,
+/// which also records it for [`GameState::Replay`].
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(InterpolationPlugin)
+            .insert_resource(Time::<Fixed>::from_hz(SIM_TICK_HZ))
+            .init_resource::<AppBootTime>()
+            .init_state::<GameState>()
+            .add_computed_state::<InRun>()
+            .add_sub_state::<PauseState>()
+            .add_event::<WindowFocused>()
+            .insert_resource(Score::default())
+            .init_resource::<RunStats>()
+            .insert_resource(RunSeed::random())
+            .insert_resource(RunRng(ChaCha8Rng::seed_from_u64(0)))
+            .insert_resource(SeedEntry::default())
+            .insert_resource(SpawnTimer(Timer::from_seconds(
+                SPAWN_INTERVAL_BASE,
+                TimerMode::Repeating,
+            )))
+            .insert_resource(OrientationState::default())
+            .init_resource::<RunRecorder>()
+            .init_resource::<LastRun>()
+            .init_resource::<ReplayCursor>()
+            .init_resource::<BestGhost>()
+            .init_resource::<GhostRecorder>()
+            .init_resource::<SaveStore>()
+            .init_resource::<ProfileLoad>()
+            .init_resource::<RunHistory>()
+            .init_resource::<PlayerName>()
+            .init_resource::<LeaderboardClient>()
+            .init_resource::<Leaderboard>()
+            .init_asset::<DifficultyProfile>()
+            .init_asset_loader::<DifficultyLoader>()
+            .init_resource::<DifficultyPresets>()
+            .init_resource::<Difficulty>()
+            .init_resource::<ControlScheme>()
+            .init_resource::<DifficultyProfile>()
+            .init_resource::<WaveCooldown>()
+            .init_resource::<Reach>()
+            .init_resource::<ActiveEffects>()
+            .init_resource::<PowerUpTimer>()
+            .init_resource::<Coins>()
+            .init_resource::<CoinTimer>()
+            .init_resource::<Combo>()
+            .init_resource::<ObstaclePool>()
+            .init_resource::<ActivePad>()
+            .init_resource::<InputMap>()
+            .init_resource::<ActiveDevice>()
+            .init_resource::<GestureTracker>()
+            .init_resource::<MotionInput>()
+            .init_resource::<TiltSettings>()
+            .init_resource::<TiltState>()
+            .init_resource::<TiltAngle>()
+            .add_event::<Gesture>()
+            .init_resource::<MouseMode>()
+            .init_resource::<Sensitivity>()
+            .init_resource::<SensitivityDevice>()
+            .init_resource::<Rebinding>()
+            .add_systems(Startup, fill_obstacle_pool)
+            // Loading
+            .add_systems(
+                OnEnter(GameState::Loading),
+                (start_profile_load, start_difficulty_load),
+            )
+            .add_systems(
+                Update,
+                (finish_profile_load, finish_loading)
+                    .chain()
+                    .run_if(in_state(GameState::Loading)),
+            )
+            // Menu
+            .add_systems(OnEnter(GameState::Menu), enter_menu)
+            .add_systems(
+                Update,
+                (
+                    menu_seed_input,
+                    menu_start,
+                    menu_open_leaderboard,
+                    menu_open_bindings,
+                    menu_cycle_difficulty,
+                    menu_cycle_controls,
+                    menu_cycle_mouse_mode,
+                    menu_adjust_sensitivity,
+                    menu_toggle_tilt,
+                    menu_adjust_tilt,
+                    first_update_probe,
+                )
+                    .after(recognize_gestures)
+                    .run_if(in_state(GameState::Menu)),
+            )
+            .add_systems(
+                Update,
+                (drop_stale_ghost, save_profile)
+                    .run_if(in_state(GameState::Menu).and_then(resource_changed::<Difficulty>)),
+            )
+            .add_systems(
+                Update,
+                save_profile
+                    .run_if(in_state(GameState::Menu).and_then(resource_changed::<ControlScheme>)),
+            )
+            .add_systems(
+                Update,
+                save_profile
+                    .run_if(in_state(GameState::Menu).and_then(resource_changed::<MouseMode>)),
+            )
+            .add_systems(
+                Update,
+                save_profile
+                    .run_if(in_state(GameState::Menu).and_then(resource_changed::<Sensitivity>)),
+            )
+            .add_systems(
+                Update,
+                save_profile
+                    .run_if(in_state(GameState::Menu).and_then(resource_changed::<TiltSettings>)),
+            )
+            // Leaderboard
+            .add_systems(OnEnter(GameState::Leaderboard), fetch_leaderboard)
+            .add_systems(
+                Update,
+                leaderboard_input
+                    .after(recognize_gestures)
+                    .run_if(in_state(GameState::Leaderboard)),
+            )
+            .add_systems(
+                OnExit(GameState::Leaderboard),
+                save_profile.run_if(resource_changed::<PlayerName>),
+            )
+            .add_systems(Update, poll_leaderboard)
+            // Bindings
+            .add_systems(OnEnter(GameState::Bindings), enter_bindings)
+            .add_systems(Update, bindings_input.run_if(in_state(GameState::Bindings)))
+            .add_systems(
+                OnExit(GameState::Bindings),
+                save_profile.run_if(resource_changed::<InputMap>),
+            )
+            // Playing / Replay
+            .add_systems(
+                OnEnter(InRun),
+                (
+                    start_replay.run_if(in_state(GameState::Replay)),
+                    apply_difficulty,
+                    (
+                        enter_playing,
+                        spawn_ghost,
+                        reset_reach,
+                        reset_powerups,
+                        reset_coins,
+                        reset_combo,
+                        spawn_track,
+                    ),
+                )
+                    .chain(),
+            )
+            .add_systems(OnEnter(GameState::Playing), start_recording)
+            .add_systems(OnExit(GameState::Replay), end_replay)
+            .add_systems(
+                Update,
+                (
+                    track_active_device,
+                    recognize_gestures,
+                    poll_motion,
+                    // Tilt goes last: while on, it owns sideways steering.
+                    ((player_input, gamepad_input), tilt_input).chain().run_if(
+                        in_state(GameState::Playing).and_then(in_state(PauseState::Running)),
+                    ),
+                )
+                    .chain(),
+            )
+            .add_systems(Update, grab_cursor)
+            .add_systems(
+                Update,
+                forget_gestures
+                    .run_if(state_changed::<GameState>.or_else(state_changed::<PauseState>))
+                    .before(recognize_gestures),
+            )
+            .add_systems(Update, pause_input.run_if(in_state(InRun)))
+            .add_systems(OnEnter(PauseState::Paused), stop_time)
+            .add_systems(OnExit(PauseState::Paused), resume_time)
+            .add_systems(
+                FixedUpdate,
+                (
+                    (
+                        record_input.run_if(in_state(GameState::Playing)),
+                        feed_replay_input.run_if(in_state(GameState::Replay)),
+                    ),
+                    move_player,
+                    set_run_speed,
+                    spawn_obstacles,
+                    spawn_powerups,
+                    spawn_coins,
+                    move_obstacles,
+                    scroll_track,
+                    pull_powerups,
+                    advance_reach,
+                    (record_ghost, advance_ghost).chain(),
+                    collect_powerups,
+                    collect_coins,
+                    collision_system,
+                    near_miss_system,
+                    score_system,
+                    advance_run_clock,
+                    tick_effects,
+                    despawn_passed_obstacles,
+                )
+                    .chain()
+                    .distributive_run_if(simulation_running),
+            )
+            .add_systems(
+                OnExit(GameState::Playing),
+                (
+                    finish_recording,
+                    keep_best_ghost,
+                    (record_finished_run, bank_coins, save_profile).chain(),
+                    // State already holds where the run went: a run quit from
+                    // the pause menu goes back to Menu and isn't submitted.
+                    submit_finished_run.run_if(in_state(GameState::GameOver)),
+                ),
+            )
+            .add_systems(
+                OnExit(InRun),
+                (
+                    exit_playing,
+                    despawn_ghost,
+                    clear_powerups,
+                    clear_coins,
+                    clear_track,
+                ),
+            )
+            // GameOver
+            .add_systems(
+                Update,
+                (game_over_restart, game_over_watch_replay)
+                    .after(recognize_gestures)
+                    .run_if(in_state(GameState::GameOver)),
+            )
+            .add_systems(First, (update_orientation, auto_pause).chain())
+            .add_systems(Update, track_gamepads);
+    }
+}
+
+/// True during an unpaused run while no transition is queued. Several fixed
+/// ticks can run in one frame, so ticks after a fatal hit must not keep
+/// simulating.
+fn simulation_running(
+    state: Res<State<GameState>>,
+    next: Res<NextState<GameState>>,
+    pause: Option<Res<State<PauseState>>>,
+) -> bool {
+    matches!(state.get(), GameState::Playing | GameState::Replay)
+        && matches!(*next, NextState::Unchanged)
+        && pause.is_some_and(|p| *p.get() == PauseState::Running)
+}
+
+/// Leaves Loading once the profile and every difficulty preset are in.
+fn finish_loading(
+    load: Res<ProfileLoad>,
+    asset_server: Res<AssetServer>,
+    presets: Res<DifficultyPresets>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if profile_loaded(&load) && difficulty_presets_settled(&asset_server, &presets) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn first_update_probe(mut bt: ResMut<AppBootTime>) {
+    if !bt.first_update_logged {
+        info!("[boot] first update tick (+{:?})", bt.app_start.elapsed());
+        bt.first_update_logged = true;
+    }
+}
+
+// --- Menu ---
+fn enter_menu(mut entry: ResMut<SeedEntry>, bt: Res<AppBootTime>) {
+    info!("[boot] menu: enter (+{:?})", bt.app_start.elapsed());
+    entry.0.clear();
+}
+
+fn menu_seed_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut seed: ResMut<RunSeed>,
+    mut entry: ResMut<SeedEntry>,
+    orientation: Res<OrientationState>,
+) {
+    if orientation.is_portrait {
+        return;
+    }
+    if keys.just_pressed(KeyCode::KeyR) {
+        entry.0.clear();
+        *seed = RunSeed::random();
+        return;
+    }
+    let mut edited = false;
+    for key in keys.get_just_pressed() {
+        if let Some(digit) = key_digit(*key) {
+            if entry.0.len() < SEED_MAX_DIGITS {
+                entry.0.push(digit);
+                edited = true;
+            }
+        } else if *key == KeyCode::Backspace {
+            edited |= entry.0.pop().is_some();
+        }
+    }
+    if edited {
+        seed.0 = entry.0.parse().unwrap_or(0);
+    }
+}
+
+pub(crate) fn key_digit(key: KeyCode) -> Option<char> {
+    let digit = match key {
+        KeyCode::Digit0 | KeyCode::Numpad0 => '0',
+        KeyCode::Digit1 | KeyCode::Numpad1 => '1',
+        KeyCode::Digit2 | KeyCode::Numpad2 => '2',
+        KeyCode::Digit3 | KeyCode::Numpad3 => '3',
+        KeyCode::Digit4 | KeyCode::Numpad4 => '4',
+        KeyCode::Digit5 | KeyCode::Numpad5 => '5',
+        KeyCode::Digit6 | KeyCode::Numpad6 => '6',
+        KeyCode::Digit7 | KeyCode::Numpad7 => '7',
+        KeyCode::Digit8 | KeyCode::Numpad8 => '8',
+        KeyCode::Digit9 | KeyCode::Numpad9 => '9',
+        _ => return None,
+    };
+    Some(digit)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn menu_start(
+    mut gestures: EventReader<Gesture>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    map: Res<InputMap>,
+    pads: Res<ButtonInput<GamepadButton>>,
+    buttons: Query<(&Interaction, &Node, &GlobalTransform), With<Button>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    bt: Res<AppBootTime>,
+    orientation: Res<OrientationState>,
+) {
+    if orientation.is_portrait {
+        return;
+    }
+    // Taps and clicks on a menu button belong to that button. A tap is only
+    // recognised once the button has let go, so go by where it landed. A
+    // double tap is ignored; its first tap already started the run.
+    let over_button = |at: Vec2| {
+        buttons
+            .iter()
+            .any(|(_, node, transform)| node.logical_rect(transform).contains(at))
+    };
+    let touched = gestures
+        .read()
+        .any(|g| matches!(g, Gesture::Tap(at) if !over_button(*at)));
+    let on_button = buttons.iter().any(|(i, ..)| *i != Interaction::None);
+    let clicked = mouse.just_pressed(MouseButton::Left) && !on_button;
+    let keyed = map.just_pressed(&keys, Action::Confirm);
+    let padded = pad_just_pressed(&pads, &CONFIRM_BUTTONS);
+
+    if touched || clicked || keyed || padded {
+        info!(
+            "[boot] menu: input -> request Playing (+{:?})",
+            bt.app_start.elapsed()
+        );
+        next_state.set(GameState::Playing);
+    }
+}
+
+// --- Playing ---
+#[allow(clippy::too_many_arguments)]
+fn enter_playing(
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    mut stats: ResMut<RunStats>,
+    mut spawn_timer: ResMut<SpawnTimer>,
+    mut cooldown: ResMut<WaveCooldown>,
+    seed: Res<RunSeed>,
+    mut rng: ResMut<RunRng>,
+    profile: Res<DifficultyProfile>,
+    bt: Res<AppBootTime>,
+) {
+    info!(
+        "[boot] playing: enter seed={} (+{:?})",
+        seed.0,
+        bt.app_start.elapsed()
+    );
+    // Reset score, clock, timer and the run's RNG stream
+    score.value = 0.0;
+    *stats = RunStats::new(&profile);
+    rng.0 = ChaCha8Rng::seed_from_u64(seed.0);
+    spawn_timer
+        .0
+        .set_duration(Duration::from_secs_f32(profile.spawn_interval_base));
+    spawn_timer.0.reset();
+    cooldown.0 = 0.0;
+
+    // Player (mesh is attached by the view plugin)
+    let start = Vec3::new(0.0, PLAYER_SIZE.y * 0.5, PLAYER_Z);
+    commands.spawn((
+        SpatialBundle::from_transform(Transform::from_translation(start)),
+        SimPosition::new(start),
+        Player {
+            target_x: 0.0,
+            action: None,
+        },
+        Stance::Running,
+    ));
+}
+
+/// `x` moved one step in `dir`: to the next lane centre in lane mode, or by
+/// [`KEY_STEP_X`] on an open track.
+pub(crate) fn step_target(lanes: Option<Lanes>, x: f32, dir: i32) -> f32 {
+    match lanes {
+        Some(lanes) => lanes.step(x, dir),
+        None => (x + dir as f32 * KEY_STEP_X).clamp(-TRACK_HALF_X, TRACK_HALF_X),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn player_input(
+    mut q_player: Query<(&Transform, &mut Player)>,
+    keys: Res<ButtonInput<KeyCode>>,
+    map: Res<InputMap>,
+    scheme: Res<ControlScheme>,
+    active: Res<ActiveDevice>,
+    mouse_mode: Res<MouseMode>,
+    sensitivity: Res<Sensitivity>,
+    mut motion_evs: EventReader<MouseMotion>,
+    mut mouse_x: Local<f32>,
+    mut gestures: EventReader<Gesture>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    // In lane mode every target is a lane centre.
+    let lanes = scheme.lanes();
+    let step = |x: f32, dir: i32| step_target(lanes, x, dir);
+
+    // Keyboard (desktop): discrete steps
+    for (_t, mut p) in &mut q_player {
+        if map.just_pressed(&keys, Action::MoveLeft) {
+            p.target_x = step(p.target_x, -1);
+        }
+        if map.just_pressed(&keys, Action::MoveRight) {
+            p.target_x = step(p.target_x, 1);
+        }
+        if map.just_pressed(&keys, Action::Jump) {
+            p.action = Some(VerticalAction::Jump);
+        }
+        if map.just_pressed(&keys, Action::Slide) {
+            p.action = Some(VerticalAction::Slide);
+        }
+    }
+
+    // Mouse (desktop): only while it's the last device used, so a resting
+    // cursor doesn't undo key steps
+    let motion_x: f32 = motion_evs.read().map(|ev| ev.delta.x).sum();
+    if active.0 == Some(InputDevice::Mouse) {
+        match *mouse_mode {
+            MouseMode::Absolute => {
+                // Cursor to track position, the track centred in the window
+                let cursor = windows
+                    .get_single()
+                    .ok()
+                    .and_then(|w| Some((w.cursor_position()?, w.width())));
+                if let Some((cursor, width)) = cursor {
+                    let dx_px = cursor.x - width * 0.5;
+                    let x = (dx_px * sensitivity.mouse).clamp(-TRACK_HALF_X, TRACK_HALF_X);
+                    for (_t, mut p) in &mut q_player {
+                        p.target_x = lanes.map_or(x, |lanes| lanes.snap(x));
+                    }
+                }
+            }
+            MouseMode::Relative if motion_x != 0.0 => {
+                // Movement accumulates off-lane so small moves add up to a
+                // lane change; other devices moving the player reset it
+                for (_t, mut p) in &mut q_player {
+                    if lanes.map_or(*mouse_x, |lanes| lanes.snap(*mouse_x)) != p.target_x {
+                        *mouse_x = p.target_x;
+                    }
+                    *mouse_x = (*mouse_x + motion_x * sensitivity.mouse)
+                        .clamp(-TRACK_HALF_X, TRACK_HALF_X);
+                    p.target_x = lanes.map_or(*mouse_x, |lanes| lanes.snap(*mouse_x));
+                }
+            }
+            MouseMode::Relative => {}
+        }
+    }
+
+    // Touch (mobile): drags steer continuously, or in lane mode a
+    // horizontal swipe or a tap on either half of the screen steps a lane;
+    // vertical swipes jump (up) or slide (down)
+    for gesture in gestures.read() {
+        match *gesture {
+            Gesture::Drag(delta) if lanes.is_none() => {
+                for (_t, mut p) in &mut q_player {
+                    p.target_x = (p.target_x + delta.x * sensitivity.touch)
+                        .clamp(-TRACK_HALF_X, TRACK_HALF_X);
+                }
+            }
+            Gesture::Swipe(direction) => {
+                let (action, dir) = match direction {
+                    SwipeDirection::Up => (Some(VerticalAction::Jump), 0),
+                    SwipeDirection::Down => (Some(VerticalAction::Slide), 0),
+                    SwipeDirection::Left => (None, -1),
+                    SwipeDirection::Right => (None, 1),
+                };
+                for (_t, mut p) in &mut q_player {
+                    if action.is_some() {
+                        p.action = action;
+                    } else if lanes.is_some() {
+                        p.target_x = step(p.target_x, dir);
+                    }
+                }
+            }
+            Gesture::Tap(position) if lanes.is_some() => {
+                if let Ok(window) = windows.get_single() {
+                    let dir = if position.x < window.width() * 0.5 {
+                        -1
+                    } else {
+                        1
+                    };
+                    for (_t, mut p) in &mut q_player {
+                        p.target_x = step(p.target_x, dir);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn move_player(
+    time: Res<Time>,
+    profile: Res<DifficultyProfile>,
+    mut q: Query<(&mut Player, &mut Stance, &mut SimPosition)>,
+) {
+    for (mut p, mut stance, mut pos) in &mut q {
+        if let Some(action) = p.action.take() {
+            *stance = stance.start(action);
+        }
+        *stance = stance.advance(time.delta_seconds());
+        pos.current.y = stance.centre_y();
+
+        let target_x = p.target_x;
+        let dx = target_x - pos.current.x;
+        let step = profile.player_lerp_speed * time.delta_seconds();
+        if dx.abs() <= step {
+            pos.current.x = target_x;
+        } else if dx > 0.0 {
+            pos.current.x += step;
+        } else {
+            pos.current.x -= step;
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_obstacles(
+    mut commands: Commands,
+    time: Res<Time>,
+    stats: Res<RunStats>,
+    profile: Res<DifficultyProfile>,
+    mut timer: ResMut<SpawnTimer>,
+    mut cooldown: ResMut<WaveCooldown>,
+    mut rng: ResMut<RunRng>,
+    reach: Res<Reach>,
+    scheme: Res<ControlScheme>,
+    q_obstacles: Query<(&SimPosition, &ObstacleKind), With<Obstacle>>,
+    mut pool: ResMut<ObstaclePool>,
+    bt: Res<AppBootTime>,
+    mut first_spawn_logged: Local<bool>,
+) {
+    let elapsed_seconds = stats.elapsed;
+    let target_interval = (profile.spawn_interval_base
+        - elapsed_seconds * profile.spawn_interval_decay_per_sec)
+        .max(profile.spawn_interval_min);
+    timer
+        .0
+        .set_duration(Duration::from_secs_f32(target_interval + cooldown.0));
+
+    if timer.0.tick(time.delta()).just_finished() {
+        // Only spawn waves that leave a path past everything already on the track.
+        let speed = stats.speed;
+        let lookahead = Lookahead::new(&profile, scheme.lanes(), speed, time.delta_seconds());
+        let on_track: Vec<(Vec2, ObstacleKind)> = q_obstacles
+            .iter()
+            .map(|(p, kind)| (p.current.xz(), *kind))
+            .collect();
+        let fair = |kind: ObstacleKind, layout: &[Vec2]| {
+            let mut incoming = on_track.clone();
+            incoming.extend(
+                layout
+                    .iter()
+                    .map(|o| (Vec2::new(o.x, OBSTACLE_START_Z - o.y), kind)),
+            );
+            lookahead.survivable(&reach, &incoming)
+        };
+        let tier = waves::tier(elapsed_seconds, profile.start_tier);
+        let Some((kind, layout)) = fair_wave(&mut rng.0, tier, scheme.lanes(), fair) else {
+            info!("[fairness] no survivable wave at {elapsed_seconds:.1}s; skipping spawn");
+            cooldown.0 = 0.0;
+            return;
+        };
+
+        // Mesh and material are attached by the view plugin from `ObstacleAssets`.
+        for offset in &layout {
+            let at = Vec3::new(offset.x, kind.centre_y(), OBSTACLE_START_Z - offset.y);
+            pool.spawn(&mut commands, kind, at);
+        }
+        let depth = layout.iter().map(|o| o.y).fold(0.0, f32::max);
+        cooldown.0 = depth / speed;
+
+        if !*first_spawn_logged {
+            info!(
+                "[boot] first obstacle spawned (+{:?})",
+                bt.app_start.elapsed()
+            );
+            *first_spawn_logged = true;
+        }
+    }
+}
+
+/// Everything that rides the track towards the player.
+type OnTrack = Or<(With<Obstacle>, With<PowerUp>, With<Coin>)>;
+
+/// Follows the difficulty curve; everything that moves with the track this
+/// tick reads [`RunStats::speed`].
+fn set_run_speed(profile: Res<DifficultyProfile>, mut stats: ResMut<RunStats>) {
+    stats.speed = obstacle_speed(stats.elapsed, &profile);
+}
+
+fn move_obstacles(time: Res<Time>, stats: Res<RunStats>, mut q: Query<&mut SimPosition, OnTrack>) {
+    for mut pos in &mut q {
+        pos.current.z += stats.speed * time.delta_seconds();
+    }
+}
+
+/// Runs after collision so an obstacle that crosses the player and leaves
+/// the track in the same tick is still checked. Obstacles go back to the
+/// pool and count as dodged; pickups are despawned.
+fn despawn_passed_obstacles(
+    mut commands: Commands,
+    mut pool: ResMut<ObstaclePool>,
+    mut stats: ResMut<RunStats>,
+    q: Query<(Entity, &SimPosition, Has<Obstacle>), OnTrack>,
+) {
+    for (e, pos, obstacle) in &q {
+        if pos.current.z <= OBSTACLE_DESPAWN_Z {
+            continue;
+        }
+        if obstacle {
+            stats.dodged += 1;
+            pool.release(&mut commands, e);
+        } else {
+            commands.entity(e).despawn();
+        }
+    }
+}
+
+fn collision_system(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut score: ResMut<Score>,
+    mut effects: ResMut<ActiveEffects>,
+    mut pool: ResMut<ObstaclePool>,
+    q_player: Query<(&SimPosition, &Stance), With<Player>>,
+    q_obstacles: Query<(Entity, &SimPosition, &ObstacleKind), With<Obstacle>>,
+) {
+    let Ok((player_pos, stance)) = q_player.get_single() else {
+        return;
+    };
+
+    // AABB overlap on all three axes, swept over the whole tick
+    let hits: Vec<Entity> = q_obstacles
+        .iter()
+        .filter(|(_, op, kind)| {
+            swept_overlap(player_pos, op, hit_half_extents(stance.size(), kind.size()))
+        })
+        .map(|(e, _, _)| e)
+        .collect();
+    if hits.is_empty() {
+        return;
+    }
+    // A shield takes the whole hit, however many obstacles are in it.
+    if effects.consume(PowerUpKind::Shield) {
+        for e in hits {
+            pool.release(&mut commands, e);
+        }
+        return;
+    }
+    // Game over
+    if score.value > score.best {
+        score.best = score.value;
+    }
+    next_state.set(GameState::GameOver);
+}
+
+fn score_system(time: Res<Time>, effects: Res<ActiveEffects>, mut score: ResMut<Score>) {
+    score.value += time.delta_seconds() * SCORE_PER_SECOND * effects.score_factor();
+}
+
+fn advance_run_clock(time: Res<Time>, mut stats: ResMut<RunStats>) {
+    let dt = time.delta_seconds();
+    stats.elapsed += dt;
+    stats.distance += stats.speed * dt;
+}
+
+fn exit_playing(
+    mut commands: Commands,
+    mut pool: ResMut<ObstaclePool>,
+    q_player: Query<Entity, With<Player>>,
+    q_obstacles: Query<Entity, With<Obstacle>>,
+) {
+    for e in &q_player {
+        commands.entity(e).despawn_recursive();
+    }
+    for e in &q_obstacles {
+        pool.release(&mut commands, e);
+    }
+}
+
+// --- Game Over ---
+#[allow(clippy::too_many_arguments)]
+fn game_over_restart(
+    mut gestures: EventReader<Gesture>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    map: Res<InputMap>,
+    pads: Res<ButtonInput<GamepadButton>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut seed: ResMut<RunSeed>,
+    orientation: Res<OrientationState>,
+) {
+    if orientation.is_portrait {
+        return;
+    }
+    let (mut tapped, mut held) = (false, false);
+    for gesture in gestures.read() {
+        match gesture {
+            Gesture::Tap(_) => tapped = true,
+            Gesture::LongPress(_) => held = true,
+            // Its first tap has already restarted.
+            Gesture::DoubleTap(_) => {}
+            _ => {}
+        }
+    }
+    // Retry (or a long press) restarts on the same seed; anything else rolls
+    // a fresh one.
+    if map.just_pressed(&keys, Action::Retry) || held {
+        next_state.set(GameState::Playing);
+        return;
+    }
+    let clicked = mouse.just_pressed(MouseButton::Left);
+    let keyed = map.just_pressed(&keys, Action::Confirm);
+    let padded = pad_just_pressed(&pads, &CONFIRM_BUTTONS);
+
+    if tapped || clicked || keyed || padded {
+        *seed = RunSeed::random();
+        next_state.set(GameState::Playing);
+    }
+}
+
+fn update_orientation(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut orientation: ResMut<OrientationState>,
+) {
+    if let Ok(window) = windows.get_single() {
+        let size = window.resolution.size();
+        let portrait = size.y > size.x;
+        orientation.is_portrait = portrait;
+    }
+}
@@ -0,0 +1,108 @@
+//! Gamepad input, for Android TV remotes and desktop controllers.
+//!
+//! One pad steers at a time: the first to connect, until it disconnects and
+//! another takes over. Losing the steering pad mid-run pauses the run. The
+//! left stick places the player across the track like the cursor does, the
+//! D-pad steps like the arrow keys, and any pad's South or Start button
+//! works the menus.
+
+use bevy::input::gamepad::{GamepadAxisChangedEvent, GamepadConnectionEvent};
+use bevy::prelude::*;
+
+use crate::devices::Sensitivity;
+use crate::game::{step_target, Player, VerticalAction, TRACK_HALF_X};
+use crate::lanes::ControlScheme;
+use crate::pause::PauseState;
+
+/// Buttons that start a run from the menu or the game over screen.
+pub const CONFIRM_BUTTONS: [GamepadButtonType; 2] =
+    [GamepadButtonType::South, GamepadButtonType::Start];
+
+/// The pad that steers, if any is connected.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ActivePad(pub Option<Gamepad>);
+
+/// True if any connected pad just pressed one of `types`.
+pub(crate) fn pad_just_pressed(
+    buttons: &ButtonInput<GamepadButton>,
+    types: &[GamepadButtonType],
+) -> bool {
+    buttons
+        .get_just_pressed()
+        .any(|b| types.contains(&b.button_type))
+}
+
+/// Hands steering to a newly connected pad if none has it, and to another
+/// connected pad (or none) when the steering pad goes.
+pub(crate) fn track_gamepads(
+    mut connection_evs: EventReader<GamepadConnectionEvent>,
+    gamepads: Res<Gamepads>,
+    mut active: ResMut<ActivePad>,
+    pause: Option<Res<State<PauseState>>>,
+    mut next_pause: ResMut<NextState<PauseState>>,
+) {
+    for ev in connection_evs.read() {
+        if ev.connected() {
+            if active.0.is_none() {
+                info!("[gamepad] {:?} steering", ev.gamepad);
+                active.0 = Some(ev.gamepad);
+            }
+        } else if active.0 == Some(ev.gamepad) {
+            active.0 = gamepads.iter().find(|pad| *pad != ev.gamepad);
+            info!("[gamepad] {:?} gone; steering: {:?}", ev.gamepad, active.0);
+            if pause
+                .as_ref()
+                .is_some_and(|p| *p.get() == PauseState::Running)
+            {
+                info!("[pause] auto-pause: gamepad disconnected");
+                next_pause.set(PauseState::Paused);
+            }
+        }
+    }
+}
+
+/// Steers with the steering pad: stick for position, D-pad for steps, South
+/// or D-pad up to jump, East or D-pad down to slide.
+pub(crate) fn gamepad_input(
+    active: Res<ActivePad>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    mut axis_evs: EventReader<GamepadAxisChangedEvent>,
+    scheme: Res<ControlScheme>,
+    sensitivity: Res<Sensitivity>,
+    mut q_player: Query<&mut Player>,
+) {
+    let Some(pad) = active.0 else {
+        axis_evs.clear();
+        return;
+    };
+    let lanes = scheme.lanes();
+    let pressed = |types: &[GamepadButtonType]| {
+        types
+            .iter()
+            .any(|t| buttons.just_pressed(GamepadButton::new(pad, *t)))
+    };
+    // Only a moving stick steers, so a resting one leaves the D-pad alone.
+    let stick = axis_evs
+        .read()
+        .filter(|ev| ev.gamepad == pad && ev.axis_type == GamepadAxisType::LeftStickX)
+        .fold(None, |_, ev| Some(ev.value));
+
+    for mut p in &mut q_player {
+        if let Some(value) = stick {
+            let x = (value * sensitivity.stick * TRACK_HALF_X).clamp(-TRACK_HALF_X, TRACK_HALF_X);
+            p.target_x = lanes.map_or(x, |lanes| lanes.snap(x));
+        }
+        if pressed(&[GamepadButtonType::DPadLeft]) {
+            p.target_x = step_target(lanes, p.target_x, -1);
+        }
+        if pressed(&[GamepadButtonType::DPadRight]) {
+            p.target_x = step_target(lanes, p.target_x, 1);
+        }
+        if pressed(&[GamepadButtonType::South, GamepadButtonType::DPadUp]) {
+            p.action = Some(VerticalAction::Jump);
+        }
+        if pressed(&[GamepadButtonType::East, GamepadButtonType::DPadDown]) {
+            p.action = Some(VerticalAction::Slide);
+        }
+    }
+}
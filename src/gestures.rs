@@ -0,0 +1,165 @@
+//! Touch gestures: raw [`TouchInput`] turned into typed [`Gesture`] events.
+//!
+//! One finger is followed at a time; others are ignored until it lifts. A
+//! touch that began on an earlier screen is forgotten when the screen
+//! changes, so the tail of the swipe that ended a run can't restart the next
+//! one. Menus and gameplay read [`Gesture`]s, never touches.
+
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::prelude::*;
+
+pub const SWIPE_MIN_PX: f32 = 40.0; // travel along one axis that makes a drag a swipe
+pub const TAP_SLOP_PX: f32 = 20.0; // wander allowed in a tap or long press
+pub const LONG_PRESS_SECS: f32 = 0.5; // held still this long; releasing sooner is a tap
+pub const DOUBLE_TAP_SECS: f32 = 0.3; // from one tap's release to the next's
+pub const DOUBLE_TAP_SLOP_PX: f32 = 40.0; // distance between the two taps
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    /// Towards the top of the screen.
+    Up,
+    Down,
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    /// Released before [`LONG_PRESS_SECS`] without wandering or swiping.
+    Tap(Vec2),
+    /// A tap close after and near the last one. Sent after that second
+    /// [`Gesture::Tap`], which is sent as usual.
+    DoubleTap(Vec2),
+    /// Held still for [`LONG_PRESS_SECS`]; sent while the finger is down,
+    /// and the release is then no tap.
+    LongPress(Vec2),
+    /// Travelled [`SWIPE_MIN_PX`] from where it began, mostly one way. At
+    /// most once per touch, as soon as the distance is reached.
+    Swipe(SwipeDirection),
+    /// Pixels moved since the touch's last position, for continuous steering.
+    Drag(Vec2),
+}
+
+/// The finger being followed, and the last tap for spotting double taps.
+#[derive(Resource, Default)]
+pub struct GestureTracker {
+    touch: Option<TrackedTouch>,
+    last_tap: Option<(f64, Vec2)>,
+}
+
+struct TrackedTouch {
+    id: u64,
+    start: Vec2,
+    last: Vec2,
+    /// Real time it began, seconds.
+    began: f64,
+    swiped: bool,
+    long_pressed: bool,
+    /// Left [`TAP_SLOP_PX`] of `start`, so it can't tap or long-press.
+    wandered: bool,
+}
+
+fn swipe_direction(travel: Vec2) -> Option<SwipeDirection> {
+    // Screen Y grows downwards.
+    if travel.y.abs() > travel.x.abs() {
+        (travel.y.abs() >= SWIPE_MIN_PX).then_some(if travel.y < 0.0 {
+            SwipeDirection::Up
+        } else {
+            SwipeDirection::Down
+        })
+    } else {
+        (travel.x.abs() >= SWIPE_MIN_PX).then_some(if travel.x < 0.0 {
+            SwipeDirection::Left
+        } else {
+            SwipeDirection::Right
+        })
+    }
+}
+
+/// Reads this frame's touches and sends the gestures they complete. Uses
+/// real time, so a long press works on the pause screen too.
+pub(crate) fn recognize_gestures(
+    time: Res<Time<Real>>,
+    mut touch_evs: EventReader<TouchInput>,
+    mut tracker: ResMut<GestureTracker>,
+    mut gestures: EventWriter<Gesture>,
+) {
+    let now = time.elapsed_seconds_f64();
+    let tracker = &mut *tracker;
+    for ev in touch_evs.read() {
+        match ev.phase {
+            TouchPhase::Started => {
+                if tracker.touch.is_none() {
+                    tracker.touch = Some(TrackedTouch {
+                        id: ev.id,
+                        start: ev.position,
+                        last: ev.position,
+                        began: now,
+                        swiped: false,
+                        long_pressed: false,
+                        wandered: false,
+                    });
+                }
+            }
+            TouchPhase::Moved => {
+                let Some(touch) = tracker.touch.as_mut().filter(|t| t.id == ev.id) else {
+                    continue;
+                };
+                gestures.send(Gesture::Drag(ev.position - touch.last));
+                touch.last = ev.position;
+                let travel = ev.position - touch.start;
+                touch.wandered |= travel.length() > TAP_SLOP_PX;
+                if !touch.swiped {
+                    if let Some(direction) = swipe_direction(travel) {
+                        touch.swiped = true;
+                        gestures.send(Gesture::Swipe(direction));
+                    }
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Canceled => {
+                if tracker.touch.as_ref().map(|t| t.id) != Some(ev.id) {
+                    continue;
+                }
+                let Some(touch) = tracker.touch.take() else {
+                    continue;
+                };
+                let tapped = ev.phase == TouchPhase::Ended
+                    && !touch.swiped
+                    && !touch.long_pressed
+                    && !touch.wandered
+                    && ev.position.distance(touch.start) <= TAP_SLOP_PX;
+                if !tapped {
+                    continue;
+                }
+                gestures.send(Gesture::Tap(touch.start));
+                match tracker.last_tap {
+                    Some((at, position))
+                        if now - at <= f64::from(DOUBLE_TAP_SECS)
+                            && position.distance(touch.start) <= DOUBLE_TAP_SLOP_PX =>
+                    {
+                        gestures.send(Gesture::DoubleTap(touch.start));
+                        tracker.last_tap = None;
+                    }
+                    _ => tracker.last_tap = Some((now, touch.start)),
+                }
+            }
+        }
+    }
+
+    if let Some(touch) = tracker.touch.as_mut() {
+        let held = now - touch.began >= f64::from(LONG_PRESS_SECS);
+        if held && !touch.wandered && !touch.swiped && !touch.long_pressed {
+            touch.long_pressed = true;
+            gestures.send(Gesture::LongPress(touch.start));
+        }
+    }
+}
+
+/// Drops the followed touch and any unread gestures when the screen changes.
+pub(crate) fn forget_gestures(
+    mut tracker: ResMut<GestureTracker>,
+    mut gestures: ResMut<Events<Gesture>>,
+) {
+    *tracker = GestureTracker::default();
+    gestures.clear();
+}
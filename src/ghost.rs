@@ -0,0 +1,122 @@
+//! Ghost runner: the best run's lateral trajectory, raced alongside the
+//! live player.
+//!
+//! Every tick records the player's simulated X and the distance covered. When
+//! a live run beats the stored best, its track becomes the new ghost. All runs
+//! share one speed curve, so the ghost's tick index is the live tick index;
+//! once the ghost's run ends it drops back with the track.
+
+use bevy::prelude::*;
+
+use crate::game::{Player, RunStats, OBSTACLE_DESPAWN_Z, PLAYER_SIZE, PLAYER_Z};
+use crate::interpolation::SimPosition;
+
+/// Per-tick player X of one run, plus how far that run got.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GhostTrack {
+    pub xs: Vec<f32>,
+    pub distance: f32,
+}
+
+/// Track of the best run so far; the ghost follows it.
+#[derive(Resource, Default)]
+pub struct BestGhost(pub Option<GhostTrack>);
+
+/// Track of the run in progress.
+#[derive(Resource, Default)]
+pub struct GhostRecorder(pub GhostTrack);
+
+#[derive(Component)]
+pub struct Ghost;
+
+impl GhostRecorder {
+    /// Live distance minus the ghost's final distance: negative while the
+    /// best run is still ahead, positive once it has been passed.
+    pub fn gap(&self, best: &BestGhost) -> Option<f32> {
+        best.0
+            .as_ref()
+            .map(|ghost| self.0.distance - ghost.distance)
+    }
+}
+
+pub(crate) fn spawn_ghost(
+    mut commands: Commands,
+    best: Res<BestGhost>,
+    mut recorder: ResMut<GhostRecorder>,
+) {
+    recorder.0 = GhostTrack::default();
+    let Some(track) = &best.0 else {
+        return;
+    };
+    let x = track.xs.first().copied().unwrap_or(0.0);
+    let at = Vec3::new(x, PLAYER_SIZE.y * 0.5, PLAYER_Z);
+    commands.spawn((
+        SpatialBundle::from_transform(Transform::from_translation(at)),
+        SimPosition::new(at),
+        Ghost,
+    ));
+}
+
+/// Records this tick of the live run. Runs after obstacles have moved, with
+/// the same speed they used.
+pub(crate) fn record_ghost(
+    time: Res<Time>,
+    stats: Res<RunStats>,
+    q_player: Query<&SimPosition, With<Player>>,
+    mut recorder: ResMut<GhostRecorder>,
+) {
+    let Ok(player) = q_player.get_single() else {
+        return;
+    };
+    recorder.0.xs.push(player.current.x);
+    recorder.0.distance += stats.speed * time.delta_seconds();
+}
+
+pub(crate) fn advance_ghost(
+    mut commands: Commands,
+    time: Res<Time>,
+    stats: Res<RunStats>,
+    best: Res<BestGhost>,
+    recorder: Res<GhostRecorder>,
+    mut q: Query<(Entity, &mut SimPosition), With<Ghost>>,
+) {
+    let Some(track) = &best.0 else {
+        return;
+    };
+    let tick = recorder.0.xs.len().saturating_sub(1);
+    for (e, mut pos) in &mut q {
+        match track.xs.get(tick) {
+            Some(x) => pos.current.x = *x,
+            // The best run crashed here: fall behind with the track.
+            None => {
+                pos.current.z += stats.speed * time.delta_seconds();
+                if pos.current.z > OBSTACLE_DESPAWN_Z {
+                    commands.entity(e).despawn();
+                }
+            }
+        }
+    }
+}
+
+/// Keeps the finished live run as the ghost if it went further than the best.
+pub(crate) fn keep_best_ghost(mut recorder: ResMut<GhostRecorder>, mut best: ResMut<BestGhost>) {
+    let track = std::mem::take(&mut recorder.0);
+    let is_best = best
+        .0
+        .as_ref()
+        .is_none_or(|ghost| track.distance > ghost.distance);
+    if is_best && !track.xs.is_empty() {
+        info!(
+            "[ghost] new best run: {} ticks, {:.1} m",
+            track.xs.len(),
+            track.distance
+        );
+        best.0 = Some(track);
+    }
+}
+
+pub(crate) fn despawn_ghost(mut commands: Commands, q: Query<Entity, With<Ghost>>) {
+    for e in &q {
+        commands.entity(e).despawn_recursive();
+    }
+}
@@ -0,0 +1,144 @@
+//! Windowless harness for driving [`GamePlugin`] from tests and CI.
+//!
+//! The app runs under `MinimalPlugins` with a clock that advances by a fixed
+//! step on every [`App::update`], so runs are reproducible without a GPU.
+//! Assets (the difficulty presets) load from the crate's `assets/` folder.
+
+use bevy::asset::AssetPlugin;
+use bevy::input::gamepad::{
+    GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo,
+};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::{ButtonState, InputPlugin};
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::Instant;
+use std::time::Duration;
+
+use crate::game::{GamePlugin, RunSeed};
+use crate::storage::{MemoryBackend, SaveStore};
+
+/// Frame step used by [`headless_app`] when none is given: 60 Hz.
+pub const DEFAULT_FRAME_STEP: Duration = Duration::from_nanos(16_666_667);
+/// Wall-clock limit for [`boot`]; asset IO runs on background threads.
+const BOOT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Frame limit for [`run_until_game_over`]. A minute of play at the highest
+/// tested frame rate is far longer than an idle player survives.
+pub const MAX_FRAMES: u32 = 150 * 60;
+
+/// Builds an app with [`GamePlugin`] and a manually stepped clock.
+pub fn headless_app(frame_step: Duration) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        StatesPlugin,
+        InputPlugin,
+        GamePlugin,
+    ))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(frame_step));
+    app
+}
+
+/// Updates until the menu is up. The profile and difficulty presets load
+/// asynchronously, so this can take a few frames.
+pub fn boot(app: &mut App) {
+    let deadline = Instant::now() + BOOT_TIMEOUT;
+    while state(app) != crate::GameState::Menu {
+        assert!(Instant::now() < deadline, "never reached the menu");
+        app.update();
+    }
+}
+
+/// A booted app on the menu at 60 Hz, with `seed` queued for the first run
+/// and saves going to `store`.
+pub fn booted_app(seed: u64, store: &MemoryBackend) -> App {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(RunSeed(seed))
+        .insert_resource(SaveStore::new(store.clone()));
+    boot(&mut app);
+    app
+}
+
+/// [`booted_app`] with a run started from the menu.
+pub fn started_app(seed: u64, store: &MemoryBackend) -> App {
+    let mut app = booted_app(seed, store);
+    tap_key(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), crate::GameState::Playing);
+    app
+}
+
+/// Updates until the run ends, returning the number of frames taken.
+pub fn run_until_game_over(app: &mut App) -> u32 {
+    for frame in 0..MAX_FRAMES {
+        if state(app) == crate::GameState::GameOver {
+            return frame;
+        }
+        app.update();
+    }
+    panic!("run did not end within {MAX_FRAMES} frames");
+}
+
+/// Runs `frames` updates.
+pub fn advance(app: &mut App, frames: u32) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+/// Presses and releases `key` over two frames, as a real keyboard would.
+pub fn tap_key(app: &mut App, key: KeyCode) {
+    send_key(app, key, ButtonState::Pressed);
+    app.update();
+    send_key(app, key, ButtonState::Released);
+    app.update();
+}
+
+fn send_key(app: &mut App, key_code: KeyCode, state: ButtonState) {
+    app.world_mut().send_event(KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(bevy::input::keyboard::NativeKey::Unidentified),
+        state,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+/// Plugs in pad `id` and lets the input systems register it.
+pub fn connect_gamepad(app: &mut App, id: usize) -> Gamepad {
+    let pad = Gamepad::new(id);
+    let info = GamepadInfo {
+        name: format!("Test pad {id}"),
+    };
+    let connection = GamepadConnectionEvent::new(pad, GamepadConnection::Connected(info));
+    app.world_mut()
+        .send_event(GamepadEvent::Connection(connection));
+    app.update();
+    pad
+}
+
+pub fn disconnect_gamepad(app: &mut App, pad: Gamepad) {
+    let connection = GamepadConnectionEvent::new(pad, GamepadConnection::Disconnected);
+    app.world_mut()
+        .send_event(GamepadEvent::Connection(connection));
+    app.update();
+}
+
+/// Presses and releases `button` on `pad` over two frames.
+pub fn tap_button(app: &mut App, pad: Gamepad, button: GamepadButtonType) {
+    for value in [1.0, 0.0] {
+        app.world_mut()
+            .send_event(GamepadEvent::Button(GamepadButtonChangedEvent::new(
+                pad, button, value,
+            )));
+        app.update();
+    }
+}
+
+/// The current [`GameState`](crate::GameState).
+pub fn state(app: &App) -> crate::GameState {
+    app.world()
+        .resource::<State<crate::GameState>>()
+        .get()
+        .clone()
+}
@@ -0,0 +1,215 @@
+//! Keyboard bindings: the run's keys, and those that start, retry or replay
+//! one, are looked up through [`InputMap`], so players can move any action
+//! to keys that suit their hand or layout.
+//!
+//! Actions fall into two groups, the run and the screens around it; a key
+//! can serve one action in each, which is how Space both jumps and starts a
+//! run. Bindings are edited on the [`GameState::Bindings`] screen and saved
+//! with the profile. The menu's settings shortcuts, the leaderboard's keys
+//! and the bindings screen's own keys stay fixed, so no binding can lock the
+//! player out of putting things right; the screen says so.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::game::{GameState, OrientationState};
+
+pub const MAX_KEYS_PER_ACTION: usize = 3; // binding a fourth drops the oldest
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Slide,
+    Pause,
+    /// Back to the menu while paused.
+    Quit,
+    /// Start a run from the menu or the game over screen.
+    Confirm,
+    /// Run the same seed again from the game over screen.
+    Retry,
+    WatchReplay,
+}
+
+impl Action {
+    pub const ALL: [Action; 9] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Jump,
+        Action::Slide,
+        Action::Pause,
+        Action::Quit,
+        Action::Confirm,
+        Action::Retry,
+        Action::WatchReplay,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move left",
+            Action::MoveRight => "Move right",
+            Action::Jump => "Jump",
+            Action::Slide => "Slide",
+            Action::Pause => "Pause",
+            Action::Quit => "Quit (paused)",
+            Action::Confirm => "Start",
+            Action::Retry => "Retry seed",
+            Action::WatchReplay => "Watch replay",
+        }
+    }
+
+    pub fn default_keys(self) -> &'static [KeyCode] {
+        match self {
+            Action::MoveLeft => &[KeyCode::ArrowLeft, KeyCode::KeyA],
+            Action::MoveRight => &[KeyCode::ArrowRight, KeyCode::KeyD],
+            Action::Jump => &[KeyCode::Space, KeyCode::ArrowUp, KeyCode::KeyW],
+            Action::Slide => &[KeyCode::ArrowDown, KeyCode::KeyS],
+            Action::Pause => &[KeyCode::Escape, KeyCode::KeyP],
+            Action::Quit => &[KeyCode::KeyQ],
+            Action::Confirm => &[KeyCode::Space, KeyCode::Enter],
+            Action::Retry => &[KeyCode::KeyR],
+            Action::WatchReplay => &[KeyCode::KeyV],
+        }
+    }
+
+    /// True for actions read during a run; a key may only serve one of these,
+    /// and one of the rest.
+    fn in_run(self) -> bool {
+        !matches!(self, Action::Confirm | Action::Retry | Action::WatchReplay)
+    }
+}
+
+/// Keys bound to each action. Actions missing from a save keep their
+/// defaults; an empty list means the player unbound it.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct InputMap(BTreeMap<Action, Vec<KeyCode>>);
+
+impl InputMap {
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.0
+            .get(&action)
+            .map_or(action.default_keys(), Vec::as_slice)
+    }
+
+    pub fn just_pressed(&self, keys: &ButtonInput<KeyCode>, action: Action) -> bool {
+        self.keys(action).iter().any(|k| keys.just_pressed(*k))
+    }
+
+    /// Adds `key` to `action`, taking it from any action in the same group.
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        for other in Action::ALL {
+            if other != action && other.in_run() == action.in_run() {
+                let keys = self.keys(other);
+                if keys.contains(&key) {
+                    let kept = keys.iter().copied().filter(|k| *k != key).collect();
+                    self.0.insert(other, kept);
+                }
+            }
+        }
+        let mut keys = self.keys(action).to_vec();
+        keys.retain(|k| *k != key);
+        keys.push(key);
+        if keys.len() > MAX_KEYS_PER_ACTION {
+            keys.remove(0);
+        }
+        self.0.insert(action, keys);
+    }
+
+    pub fn clear(&mut self, action: Action) {
+        self.0.insert(action, Vec::new());
+    }
+
+    /// The keys of `action` as shown on screen.
+    pub fn describe(&self, action: Action) -> String {
+        let keys = self.keys(action);
+        if keys.is_empty() {
+            return "-".to_string();
+        }
+        keys.iter()
+            .map(|k| key_name(*k))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+/// Short on-screen name for `key`: `KeyA` is "A", `ArrowLeft` is "Left".
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    for prefix in ["Key", "Digit", "Arrow"] {
+        match name.strip_prefix(prefix) {
+            Some(rest) if !rest.is_empty() => return rest.to_string(),
+            _ => {}
+        }
+    }
+    name
+}
+
+/// Where the bindings screen's cursor is. While `listening`, the next key
+/// pressed is bound to the selected action.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rebinding {
+    pub selected: usize,
+    pub listening: bool,
+}
+
+impl Rebinding {
+    pub fn action(&self) -> Action {
+        Action::ALL[self.selected]
+    }
+}
+
+/// K on the menu opens the bindings screen.
+pub(crate) fn menu_open_bindings(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keys.just_pressed(KeyCode::KeyK) {
+        next_state.set(GameState::Bindings);
+    }
+}
+
+pub(crate) fn enter_bindings(mut rebinding: ResMut<Rebinding>) {
+    *rebinding = Rebinding::default();
+}
+
+/// Fixed keys, so no binding can lock the player out: Up and Down pick an
+/// action, Enter waits for a key to add to it, Backspace unbinds it, R
+/// restores every default and Escape goes back. Escape also cancels waiting.
+pub(crate) fn bindings_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut map: ResMut<InputMap>,
+    mut rebinding: ResMut<Rebinding>,
+    mut next_state: ResMut<NextState<GameState>>,
+    orientation: Res<OrientationState>,
+) {
+    if orientation.is_portrait {
+        return;
+    }
+    if rebinding.listening {
+        if keys.just_pressed(KeyCode::Escape) {
+            rebinding.listening = false;
+        } else if let Some(key) = keys.get_just_pressed().next() {
+            map.bind(rebinding.action(), *key);
+            rebinding.listening = false;
+        }
+        return;
+    }
+    let count = Action::ALL.len();
+    if keys.just_pressed(KeyCode::ArrowUp) {
+        rebinding.selected = (rebinding.selected + count - 1) % count;
+    }
+    if keys.just_pressed(KeyCode::ArrowDown) {
+        rebinding.selected = (rebinding.selected + 1) % count;
+    }
+    if keys.just_pressed(KeyCode::Enter) {
+        rebinding.listening = true;
+    } else if keys.just_pressed(KeyCode::Backspace) {
+        map.clear(rebinding.action());
+    } else if keys.just_pressed(KeyCode::KeyR) {
+        *map = InputMap::default();
+    } else if keys.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Menu);
+    }
+}
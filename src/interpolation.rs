@@ -0,0 +1,53 @@
+//! Fixed-tick positions and their render-side interpolation.
+//!
+//! Gameplay moves entities by writing [`SimPosition::current`] in
+//! `FixedUpdate`. `Transform` is never read by the simulation; it is blended
+//! between the last two ticks every frame so motion stays smooth at any
+//! display rate.
+
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+
+/// Position of a simulated entity at the last two fixed ticks.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct SimPosition {
+    pub previous: Vec3,
+    pub current: Vec3,
+}
+
+impl SimPosition {
+    pub fn new(at: Vec3) -> Self {
+        SimPosition {
+            previous: at,
+            current: at,
+        }
+    }
+}
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedFirst, snapshot_sim_positions)
+            .add_systems(
+                PostUpdate,
+                interpolate_transforms.before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+fn snapshot_sim_positions(mut q: Query<&mut SimPosition>) {
+    for mut p in &mut q {
+        p.previous = p.current;
+    }
+}
+
+fn interpolate_transforms(
+    fixed_time: Res<Time<Fixed>>,
+    mut q: Query<(&SimPosition, &mut Transform)>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+    for (p, mut t) in &mut q {
+        t.translation = p.previous.lerp(p.current, alpha);
+    }
+}
@@ -0,0 +1,99 @@
+//! Lane control scheme: the track split into a fixed number of lanes that
+//! the player snaps between.
+//!
+//! Keys step one lane, a tap steps towards the tapped side and a horizontal
+//! swipe steps in its direction; the cursor picks the nearest lane. Waves
+//! are laid out on lane centres. Picked on the menu and saved with the
+//! profile.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::TRACK_HALF_X;
+
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ControlScheme {
+    /// Steer anywhere on the track.
+    #[default]
+    Analog,
+    ThreeLanes,
+    FiveLanes,
+}
+
+impl ControlScheme {
+    pub const ALL: [ControlScheme; 3] = [
+        ControlScheme::Analog,
+        ControlScheme::ThreeLanes,
+        ControlScheme::FiveLanes,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ControlScheme::Analog => "Analog",
+            ControlScheme::ThreeLanes => "3 lanes",
+            ControlScheme::FiveLanes => "5 lanes",
+        }
+    }
+
+    /// The next scheme, wrapping around; the menu cycles with this.
+    pub fn next(self) -> Self {
+        match self {
+            ControlScheme::Analog => ControlScheme::ThreeLanes,
+            ControlScheme::ThreeLanes => ControlScheme::FiveLanes,
+            ControlScheme::FiveLanes => ControlScheme::Analog,
+        }
+    }
+
+    pub fn lanes(self) -> Option<Lanes> {
+        match self {
+            ControlScheme::Analog => None,
+            ControlScheme::ThreeLanes => Some(Lanes(3)),
+            ControlScheme::FiveLanes => Some(Lanes(5)),
+        }
+    }
+}
+
+/// Equal-width lanes across the whole track, numbered from the left.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lanes(pub usize);
+
+impl Lanes {
+    pub fn count(self) -> usize {
+        self.0
+    }
+
+    pub fn width(self) -> f32 {
+        2.0 * TRACK_HALF_X / self.0 as f32
+    }
+
+    pub fn centre(self, lane: usize) -> f32 {
+        -TRACK_HALF_X + (lane.min(self.0 - 1) as f32 + 0.5) * self.width()
+    }
+
+    /// Lane whose span holds `x`, clamped to the track.
+    pub fn nearest(self, x: f32) -> usize {
+        let lane = ((x + TRACK_HALF_X) / self.width()).floor();
+        lane.clamp(0.0, (self.0 - 1) as f32) as usize
+    }
+
+    pub fn snap(self, x: f32) -> f32 {
+        self.centre(self.nearest(x))
+    }
+
+    /// Centre of the lane `dir` lanes from the one holding `x`, stopping at
+    /// the edges.
+    pub fn step(self, x: f32, dir: i32) -> f32 {
+        let lane = self.nearest(x) as i32 + dir;
+        self.centre(lane.clamp(0, self.0 as i32 - 1) as usize)
+    }
+}
+
+/// C on the menu cycles the scheme.
+pub(crate) fn menu_cycle_controls(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut scheme: ResMut<ControlScheme>,
+) {
+    if keys.just_pressed(KeyCode::KeyC) {
+        *scheme = scheme.next();
+    }
+}
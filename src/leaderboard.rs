@@ -0,0 +1,313 @@
+//! Local leaderboard: the best runs on this device, with the player's name.
+//!
+//! Inside the Tauri webview the board lives in the app data directory behind
+//! the `submit_score`/`get_leaderboard` commands, which also stamp the date.
+//! Elsewhere (`trunk serve`, native tests) it is kept in memory for the
+//! session. Every finished live run is submitted; the backend keeps the top
+//! [`LEADERBOARD_LEN`].
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+use crate::game::{key_digit, GameState, OrientationState, RunSeed, RunStats, Score};
+use crate::gestures::Gesture;
+use crate::profile::PlayerName;
+use crate::storage::Pending;
+
+pub const LEADERBOARD_LEN: usize = 10; // best runs kept
+pub const NAME_MAX_CHARS: usize = 12;
+
+/// One run on the board. Field names match the Tauri side.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub score: f32,
+    pub seed: u64,
+    pub duration_secs: f32,
+    /// Seconds since the Unix epoch.
+    pub date: u64,
+}
+
+/// A finished run as sent to the backend, which adds the date.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ScoreSubmission {
+    pub name: String,
+    pub score: f32,
+    pub seed: u64,
+    pub duration_secs: f32,
+}
+
+/// The board after a fetch or submit, best first.
+pub type BoardResult = Result<Vec<LeaderboardEntry>, String>;
+
+pub trait LeaderboardBackend: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+    /// Records a run; resolves to the updated board.
+    fn submit(&self, run: ScoreSubmission) -> Pending<BoardResult>;
+    fn fetch(&self) -> Pending<BoardResult>;
+}
+
+#[derive(Resource)]
+pub struct LeaderboardClient(Box<dyn LeaderboardBackend>);
+
+impl LeaderboardClient {
+    pub fn new(backend: impl LeaderboardBackend) -> Self {
+        LeaderboardClient(Box::new(backend))
+    }
+
+    pub fn backend_name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+impl Default for LeaderboardClient {
+    #[cfg(target_arch = "wasm32")]
+    fn default() -> Self {
+        if crate::tauri_ipc::available() {
+            LeaderboardClient::new(web::TauriLeaderboard)
+        } else {
+            LeaderboardClient::new(MemoryLeaderboard::default())
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default() -> Self {
+        LeaderboardClient::new(MemoryLeaderboard::default())
+    }
+}
+
+/// What the leaderboard screen shows.
+#[derive(Resource, Default)]
+pub struct Leaderboard {
+    pub entries: Vec<LeaderboardEntry>,
+    pub loading: bool,
+    pub error: Option<String>,
+    pending: Option<Pending<BoardResult>>,
+}
+
+impl Leaderboard {
+    fn wait_for(&mut self, pending: Pending<BoardResult>) {
+        self.pending = Some(pending);
+        self.loading = true;
+    }
+}
+
+/// Highest score first; ties go to the earlier run. Keeps the top
+/// [`LEADERBOARD_LEN`].
+pub fn rank(entries: &mut Vec<LeaderboardEntry>) {
+    entries.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.date.cmp(&b.date)));
+    entries.truncate(LEADERBOARD_LEN);
+}
+
+/// In-process board. Clones share the same entries, so tests can keep a
+/// handle to inspect what the game submitted.
+#[derive(Clone, Default)]
+pub struct MemoryLeaderboard(Arc<Mutex<Vec<LeaderboardEntry>>>);
+
+impl MemoryLeaderboard {
+    pub fn entries(&self) -> Vec<LeaderboardEntry> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl LeaderboardBackend for MemoryLeaderboard {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn submit(&self, run: ScoreSubmission) -> Pending<BoardResult> {
+        let mut entries = self.0.lock().unwrap();
+        entries.push(LeaderboardEntry {
+            name: run.name,
+            score: run.score,
+            seed: run.seed,
+            duration_secs: run.duration_secs,
+            date: now_unix_secs(),
+        });
+        rank(&mut entries);
+        Pending::ready(Ok(entries.clone()))
+    }
+
+    fn fetch(&self) -> Pending<BoardResult> {
+        Pending::ready(Ok(self.entries()))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_unix_secs() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_unix_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// `YYYY-MM-DD` (UTC) for a Unix timestamp.
+pub fn format_date(unix_secs: u64) -> String {
+    // Days-to-civil conversion from Howard Hinnant's date algorithms.
+    let z = (unix_secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Submits the live run that just ended. The reply refreshes the board.
+pub(crate) fn submit_finished_run(
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    seed: Res<RunSeed>,
+    name: Res<PlayerName>,
+    client: Res<LeaderboardClient>,
+    mut board: ResMut<Leaderboard>,
+) {
+    let pending = client.0.submit(ScoreSubmission {
+        name: name.0.clone(),
+        score: score.value,
+        seed: seed.0,
+        duration_secs: stats.elapsed,
+    });
+    board.wait_for(pending);
+}
+
+pub(crate) fn fetch_leaderboard(client: Res<LeaderboardClient>, mut board: ResMut<Leaderboard>) {
+    info!("[leaderboard] fetching from {}", client.backend_name());
+    let pending = client.0.fetch();
+    board.wait_for(pending);
+}
+
+pub(crate) fn poll_leaderboard(mut board: ResMut<Leaderboard>) {
+    let Some(result) = board.pending.as_ref().and_then(Pending::poll) else {
+        return;
+    };
+    board.pending = None;
+    board.loading = false;
+    match result {
+        Ok(entries) => {
+            board.entries = entries;
+            board.error = None;
+        }
+        Err(e) => {
+            warn!("[leaderboard] request failed: {e}");
+            board.error = Some(e);
+        }
+    }
+}
+
+/// L on the menu opens the board.
+pub(crate) fn menu_open_leaderboard(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keys.just_pressed(KeyCode::KeyL) {
+        next_state.set(GameState::Leaderboard);
+    }
+}
+
+/// Letters and digits edit the player's name; Escape, Enter, a click or a tap
+/// go back to the menu.
+pub(crate) fn leaderboard_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut gestures: EventReader<Gesture>,
+    mut name: ResMut<PlayerName>,
+    mut next_state: ResMut<NextState<GameState>>,
+    orientation: Res<OrientationState>,
+) {
+    if orientation.is_portrait {
+        return;
+    }
+    for key in keys.get_just_pressed() {
+        if let Some(c) = key_letter(*key).or_else(|| key_digit(*key)) {
+            if name.0.chars().count() < NAME_MAX_CHARS {
+                name.0.push(c);
+            }
+        } else if *key == KeyCode::Backspace {
+            name.0.pop();
+        }
+    }
+    let back = keys.any_just_pressed([KeyCode::Escape, KeyCode::Enter])
+        || mouse.just_pressed(MouseButton::Left)
+        || gestures.read().any(|g| matches!(g, Gesture::Tap(_)));
+    if back {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn key_letter(key: KeyCode) -> Option<char> {
+    const LETTERS: [KeyCode; 26] = [
+        KeyCode::KeyA,
+        KeyCode::KeyB,
+        KeyCode::KeyC,
+        KeyCode::KeyD,
+        KeyCode::KeyE,
+        KeyCode::KeyF,
+        KeyCode::KeyG,
+        KeyCode::KeyH,
+        KeyCode::KeyI,
+        KeyCode::KeyJ,
+        KeyCode::KeyK,
+        KeyCode::KeyL,
+        KeyCode::KeyM,
+        KeyCode::KeyN,
+        KeyCode::KeyO,
+        KeyCode::KeyP,
+        KeyCode::KeyQ,
+        KeyCode::KeyR,
+        KeyCode::KeyS,
+        KeyCode::KeyT,
+        KeyCode::KeyU,
+        KeyCode::KeyV,
+        KeyCode::KeyW,
+        KeyCode::KeyX,
+        KeyCode::KeyY,
+        KeyCode::KeyZ,
+    ];
+    let i = LETTERS.iter().position(|k| *k == key)?;
+    Some((b'A' + i as u8) as char)
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use super::{BoardResult, LeaderboardBackend, ScoreSubmission};
+    use crate::storage::Pending;
+    use crate::tauri_ipc;
+
+    pub struct TauriLeaderboard;
+
+    fn call(cmd: &'static str, args: serde_json::Value) -> Pending<BoardResult> {
+        let pending = Pending::default();
+        let reply = pending.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = tauri_ipc::invoke(cmd, &args).await.and_then(|value| {
+                serde_json::from_value(value).map_err(|e| format!("bad {cmd} reply: {e}"))
+            });
+            reply.fulfil(result);
+        });
+        pending
+    }
+
+    impl LeaderboardBackend for TauriLeaderboard {
+        fn name(&self) -> &'static str {
+            "tauri"
+        }
+
+        fn submit(&self, run: ScoreSubmission) -> Pending<BoardResult> {
+            call("submit_score", serde_json::json!({ "entry": run }))
+        }
+
+        fn fetch(&self) -> Pending<BoardResult> {
+            call("get_leaderboard", serde_json::json!({}))
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Lane Runner: a Bevy endless runner shipped inside a Tauri webview.
+//!
+//! [`GamePlugin`] holds the rules and state flow and runs headless;
+//! [`GameViewPlugin`] adds camera, meshes and UI on top of it.
+
+pub mod coins;
+pub mod collision;
+pub mod combo;
+pub mod devices;
+pub mod difficulty;
+pub mod fairness;
+pub mod game;
+pub mod gamepad;
+pub mod gestures;
+pub mod ghost;
+pub mod headless;
+pub mod input_map;
+pub mod interpolation;
+pub mod lanes;
+pub mod leaderboard;
+pub mod motion;
+pub mod pause;
+pub mod pool;
+pub mod powerups;
+pub mod profile;
+pub mod replay;
+pub mod storage;
+#[cfg(target_arch = "wasm32")]
+pub mod tauri_ipc;
+pub mod track;
+pub mod view;
+pub mod waves;
+
+pub use game::{GamePlugin, GameState, InRun};
+pub use view::GameViewPlugin;
@@ -0,0 +1,361 @@
+//! Tilt steering: rolling the phone moves the player across the track.
+//!
+//! Readings come from a [`MotionSource`] behind [`MotionInput`]. Inside the
+//! Tauri app that is the `read_tilt` command, which on Android is bridged to
+//! the gravity sensor by a small Kotlin plugin; anywhere else it is a
+//! [`ScriptedMotion`] that reports nothing until a test (or a desktop debug
+//! session) feeds it angles. Tilt is off until turned on from the menu,
+//! which also takes the way the phone is held then as level, and where the
+//! dead zone and full tilt angles are tuned. While on, tilt owns sideways
+//! steering; swipes still jump and slide. The settings are saved with the
+//! profile.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::game::{Player, TRACK_HALF_X};
+use crate::lanes::ControlScheme;
+
+pub const TILT_DEAD_ZONE: f32 = 0.05; // radians either side of level that don't steer
+pub const TILT_FULL: f32 = 0.45; // radians from level that reach the track's edge
+pub const TILT_MAX_DEAD_ZONE: f32 = 0.3;
+pub const TILT_MAX_FULL: f32 = 1.2; // highest full tilt the menu goes to
+pub const TILT_STEP: f32 = 0.05; // radians per menu press
+
+/// One sensor reading, named to match the Tauri side.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Tilt {
+    /// Radians; positive when the right side of the screen dips.
+    pub roll: f32,
+}
+
+pub trait MotionSource: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+    /// The latest reading, or `None` with no sensor or none reported yet.
+    /// Called once a frame while tilt is on.
+    fn read(&self) -> Option<Tilt>;
+}
+
+#[derive(Resource)]
+pub struct MotionInput(Box<dyn MotionSource>);
+
+impl MotionInput {
+    pub fn new(source: impl MotionSource) -> Self {
+        MotionInput(Box::new(source))
+    }
+
+    pub fn backend_name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+impl Default for MotionInput {
+    #[cfg(target_arch = "wasm32")]
+    fn default() -> Self {
+        if crate::tauri_ipc::available() {
+            MotionInput::new(web::TauriMotion::default())
+        } else {
+            MotionInput::new(ScriptedMotion::default())
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default() -> Self {
+        MotionInput::new(ScriptedMotion::default())
+    }
+}
+
+/// Readings set by hand or queued up. Clones share the same state, so tests
+/// can keep a handle to tilt the "phone" the game is reading.
+#[derive(Clone, Default)]
+pub struct ScriptedMotion(Arc<Mutex<Script>>);
+
+#[derive(Default)]
+struct Script {
+    queued: VecDeque<f32>,
+    current: Option<Tilt>,
+}
+
+impl ScriptedMotion {
+    /// Holds the phone at `roll` until told otherwise.
+    pub fn set(&self, roll: f32) {
+        let mut script = self.0.lock().unwrap();
+        script.queued.clear();
+        script.current = Some(Tilt { roll });
+    }
+
+    /// Plays `rolls` one reading at a time, then holds the last.
+    pub fn queue(&self, rolls: impl IntoIterator<Item = f32>) {
+        self.0.lock().unwrap().queued.extend(rolls);
+    }
+
+    /// As if the sensor went away.
+    pub fn clear(&self) {
+        let mut script = self.0.lock().unwrap();
+        script.queued.clear();
+        script.current = None;
+    }
+}
+
+impl MotionSource for ScriptedMotion {
+    fn name(&self) -> &'static str {
+        "scripted"
+    }
+
+    fn read(&self) -> Option<Tilt> {
+        let mut script = self.0.lock().unwrap();
+        if let Some(roll) = script.queued.pop_front() {
+            script.current = Some(Tilt { roll });
+        }
+        script.current
+    }
+}
+
+/// How tilt maps onto the track.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct TiltSettings {
+    pub enabled: bool,
+    /// The roll that counts as level, taken when tilt is turned on.
+    pub neutral: f32,
+    /// Radians either side of `neutral` that leave the player centred.
+    pub dead_zone: f32,
+    /// Radians from `neutral` that reach the track's edge.
+    pub full_tilt: f32,
+}
+
+impl Default for TiltSettings {
+    fn default() -> Self {
+        TiltSettings {
+            enabled: false,
+            neutral: 0.0,
+            dead_zone: TILT_DEAD_ZONE,
+            full_tilt: TILT_FULL,
+        }
+    }
+}
+
+impl TiltSettings {
+    /// Track x for `tilt`: centred inside the dead zone, then in proportion
+    /// to the angle past it, reaching the edge at `full_tilt`.
+    pub fn target_x(&self, tilt: Tilt) -> f32 {
+        let angle = tilt.roll - self.neutral;
+        let past = (angle.abs() - self.dead_zone).max(0.0);
+        let range = self.full_tilt - self.dead_zone;
+        (angle.signum() * past / range * TRACK_HALF_X).clamp(-TRACK_HALF_X, TRACK_HALF_X)
+    }
+
+    /// Puts back defaults for anything a bad save could break: a dead zone
+    /// past [`TILT_MAX_DEAD_ZONE`] or a full tilt inside the dead zone.
+    pub fn clamped(self) -> Self {
+        let default = TiltSettings::default();
+        let dead_zone = if (0.0..=TILT_MAX_DEAD_ZONE).contains(&self.dead_zone) {
+            self.dead_zone
+        } else {
+            default.dead_zone
+        };
+        let full_tilt = if self.full_tilt.is_finite() && self.full_tilt > dead_zone {
+            self.full_tilt
+        } else {
+            default.full_tilt.max(dead_zone * 2.0)
+        };
+        TiltSettings {
+            enabled: self.enabled,
+            neutral: if self.neutral.is_finite() {
+                self.neutral
+            } else {
+                default.neutral
+            },
+            dead_zone,
+            full_tilt,
+        }
+    }
+
+    /// `angle`'s setting, radians.
+    pub fn angle(self, angle: TiltAngle) -> f32 {
+        match angle {
+            TiltAngle::DeadZone => self.dead_zone,
+            TiltAngle::FullTilt => self.full_tilt,
+        }
+    }
+
+    /// Moves `angle` `steps` [`TILT_STEP`]s up (or down, if negative). The
+    /// dead zone stays within [`TILT_MAX_DEAD_ZONE`] and a step short of full
+    /// tilt, which stays within [`TILT_MAX_FULL`].
+    pub fn adjust(&mut self, angle: TiltAngle, steps: i32) {
+        let stepped = ((self.angle(angle) / TILT_STEP).round() + steps as f32) * TILT_STEP;
+        match angle {
+            TiltAngle::DeadZone => {
+                let max = (self.full_tilt - TILT_STEP).clamp(0.0, TILT_MAX_DEAD_ZONE);
+                self.dead_zone = stepped.clamp(0.0, max);
+            }
+            TiltAngle::FullTilt => {
+                let min = self.dead_zone + TILT_STEP;
+                self.full_tilt = stepped.clamp(min, TILT_MAX_FULL.max(min));
+            }
+        }
+    }
+}
+
+/// A tilt angle the menu can tune.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TiltAngle {
+    #[default]
+    DeadZone,
+    FullTilt,
+}
+
+impl TiltAngle {
+    pub fn label(self) -> &'static str {
+        match self {
+            TiltAngle::DeadZone => "Tilt dead zone",
+            TiltAngle::FullTilt => "Full tilt",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            TiltAngle::DeadZone => TiltAngle::FullTilt,
+            TiltAngle::FullTilt => TiltAngle::DeadZone,
+        }
+    }
+}
+
+/// This frame's reading, and whether the next one should become level.
+#[derive(Resource, Default)]
+pub struct TiltState {
+    pub reading: Option<Tilt>,
+    pub calibrating: bool,
+}
+
+/// T on the menu turns tilt on or off. Turning it on calibrates.
+pub(crate) fn menu_toggle_tilt(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<TiltSettings>,
+    mut state: ResMut<TiltState>,
+) {
+    if keys.just_pressed(KeyCode::KeyT) {
+        settings.enabled = !settings.enabled;
+        state.calibrating = settings.enabled;
+    }
+}
+
+/// On the menu, Z picks the dead zone or full tilt, and [ and ] step it.
+pub(crate) fn menu_adjust_tilt(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut angle: ResMut<TiltAngle>,
+    mut settings: ResMut<TiltSettings>,
+) {
+    if keys.just_pressed(KeyCode::KeyZ) {
+        *angle = angle.next();
+    }
+    if keys.just_pressed(KeyCode::BracketLeft) {
+        settings.adjust(*angle, -1);
+    }
+    if keys.just_pressed(KeyCode::BracketRight) {
+        settings.adjust(*angle, 1);
+    }
+}
+
+/// Reads the sensor while tilt is on. A reading taken while calibrating
+/// becomes the new level.
+pub(crate) fn poll_motion(
+    motion: Res<MotionInput>,
+    mut settings: ResMut<TiltSettings>,
+    mut state: ResMut<TiltState>,
+) {
+    if !settings.enabled {
+        state.reading = None;
+        return;
+    }
+    state.reading = motion.0.read();
+    if let (true, Some(tilt)) = (state.calibrating, state.reading) {
+        info!("[tilt] level at {:.3} rad", tilt.roll);
+        settings.neutral = tilt.roll;
+        state.calibrating = false;
+    }
+}
+
+/// Steers from the latest reading, snapped to a lane in lane mode.
+pub(crate) fn tilt_input(
+    settings: Res<TiltSettings>,
+    state: Res<TiltState>,
+    scheme: Res<ControlScheme>,
+    mut q_player: Query<&mut Player>,
+) {
+    let Some(tilt) = state.reading.filter(|_| settings.enabled) else {
+        return;
+    };
+    let x = settings.target_x(tilt);
+    let x = scheme.lanes().map_or(x, |lanes| lanes.snap(x));
+    for mut p in &mut q_player {
+        p.target_x = x;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use super::{MotionSource, Tilt};
+    use crate::storage::Pending;
+    use crate::tauri_ipc;
+    use bevy::prelude::*;
+    use std::sync::Mutex;
+
+    type Reply = Result<Option<Tilt>, String>;
+
+    /// Polls `read_tilt` with one call in flight at a time, answering with
+    /// the latest reply. Stops asking after an error, e.g. an app built
+    /// without the command.
+    #[derive(Default)]
+    pub struct TauriMotion(Mutex<Poll>);
+
+    #[derive(Default)]
+    struct Poll {
+        in_flight: Option<Pending<Reply>>,
+        latest: Option<Tilt>,
+        failed: bool,
+    }
+
+    fn request() -> Pending<Reply> {
+        let pending = Pending::default();
+        let reply = pending.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = tauri_ipc::invoke("read_tilt", &serde_json::json!({}))
+                .await
+                .and_then(|value| {
+                    serde_json::from_value(value).map_err(|e| format!("bad read_tilt reply: {e}"))
+                });
+            reply.fulfil(result);
+        });
+        pending
+    }
+
+    impl MotionSource for TauriMotion {
+        fn name(&self) -> &'static str {
+            "tauri"
+        }
+
+        fn read(&self) -> Option<Tilt> {
+            let mut poll = self.0.lock().unwrap();
+            match poll.in_flight.as_ref().and_then(Pending::poll) {
+                Some(Ok(tilt)) => {
+                    poll.latest = tilt;
+                    poll.in_flight = None;
+                }
+                Some(Err(e)) => {
+                    warn!("[tilt] read_tilt failed: {e}");
+                    poll.latest = None;
+                    poll.in_flight = None;
+                    poll.failed = true;
+                }
+                None => {}
+            }
+            if poll.in_flight.is_none() && !poll.failed {
+                poll.in_flight = Some(request());
+            }
+            poll.latest
+        }
+    }
+}
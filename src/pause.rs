@@ -0,0 +1,135 @@
+//! Pausing a run, by hand or automatically.
+//!
+//! [`PauseState`] only exists while [`InRun`] does, so pausing and resuming
+//! never re-run the run's setup or teardown. Virtual time stops while paused,
+//! which stops `FixedUpdate` with it. Losing window focus, the webview being
+//! hidden, turning the device to portrait and unplugging the steering
+//! gamepad all pause the run.
+
+use bevy::prelude::*;
+use bevy::window::WindowFocused;
+
+use crate::game::{GameState, InRun, OrientationState};
+use crate::gamepad::pad_just_pressed;
+use crate::input_map::{Action, InputMap};
+
+#[derive(SubStates, Default, Debug, Clone, Eq, PartialEq, Hash)]
+#[source(InRun = InRun)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// The Pause keys (Escape or P by default) or a pad's Start toggle the
+/// pause; Quit (Q) or Select quits to the menu while paused.
+pub(crate) fn pause_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    map: Res<InputMap>,
+    pads: Res<ButtonInput<GamepadButton>>,
+    pause: Res<State<PauseState>>,
+    mut next_pause: ResMut<NextState<PauseState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let paused = *pause.get() == PauseState::Paused;
+    let pad = |button| pad_just_pressed(&pads, &[button]);
+    if map.just_pressed(&keys, Action::Pause) || pad(GamepadButtonType::Start) {
+        next_pause.set(if paused {
+            PauseState::Running
+        } else {
+            PauseState::Paused
+        });
+    } else if paused && (map.just_pressed(&keys, Action::Quit) || pad(GamepadButtonType::Select)) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// Pauses a running run when the player can't be watching it. Resuming is
+/// always left to the player.
+pub(crate) fn auto_pause(
+    mut focus_evs: EventReader<WindowFocused>,
+    orientation: Res<OrientationState>,
+    pause: Option<Res<State<PauseState>>>,
+    mut next_pause: ResMut<NextState<PauseState>>,
+) {
+    // Drain every event; the latest one says whether focus is gone now.
+    let lost_focus = focus_evs.read().fold(false, |_, ev| !ev.focused);
+    let hidden = page_hidden();
+    let running = pause.is_some_and(|p| *p.get() == PauseState::Running);
+    if !running {
+        return;
+    }
+    let reason = if lost_focus {
+        "focus lost"
+    } else if hidden {
+        "page hidden"
+    } else if orientation.is_portrait {
+        "portrait"
+    } else {
+        return;
+    };
+    info!("[pause] auto-pause: {reason}");
+    next_pause.set(PauseState::Paused);
+}
+
+pub(crate) fn stop_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+pub(crate) fn resume_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+#[cfg(target_arch = "wasm32")]
+fn page_hidden() -> bool {
+    web::take_hidden()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn page_hidden() -> bool {
+    false
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Once;
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+
+    static HIDDEN: AtomicBool = AtomicBool::new(false);
+    static LISTEN: Once = Once::new();
+
+    /// True if the page was hidden or blurred since the last call. Android
+    /// webviews don't reliably report focus to winit, and a hidden page stops
+    /// rendering, so the DOM events are latched here for the next frame.
+    pub fn take_hidden() -> bool {
+        LISTEN.call_once(listen);
+        HIDDEN.swap(false, Ordering::Relaxed)
+    }
+
+    fn listen() {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let on_visibility = Closure::<dyn Fn()>::new(|| {
+            let hidden = web_sys::window()
+                .and_then(|w| w.document())
+                .is_some_and(|d| d.hidden());
+            if hidden {
+                HIDDEN.store(true, Ordering::Relaxed);
+            }
+        });
+        if let Some(document) = window.document() {
+            let _ = document.add_event_listener_with_callback(
+                "visibilitychange",
+                on_visibility.as_ref().unchecked_ref(),
+            );
+        }
+        on_visibility.forget();
+
+        let on_blur = Closure::<dyn Fn()>::new(|| HIDDEN.store(true, Ordering::Relaxed));
+        let _ = window.add_event_listener_with_callback("blur", on_blur.as_ref().unchecked_ref());
+        on_blur.forget();
+    }
+}
@@ -0,0 +1,97 @@
+//! Obstacle pool: passed obstacles are parked out of sight and handed back
+//! out by the next waves, instead of being despawned and spawned again.
+//!
+//! A parked obstacle keeps its position, transform and mesh components but
+//! loses the [`Obstacle`] marker, so no gameplay query sees it. The marker
+//! uses sparse-set storage, so toggling it doesn't move table rows.
+
+use bevy::prelude::*;
+
+use crate::combo::Grazed;
+use crate::game::{Obstacle, ObstacleKind};
+use crate::interpolation::SimPosition;
+
+pub const POOL_PREALLOC: usize = 64; // parked obstacles made at startup; about twice a Hard run's peak
+pub const PARKED_AT: Vec3 = Vec3::new(0.0, -100.0, 1000.0); // out of sight, behind the camera
+
+#[derive(Resource)]
+pub struct ObstaclePool {
+    free: Vec<Entity>,
+    recycle: bool,
+}
+
+impl Default for ObstaclePool {
+    fn default() -> Self {
+        ObstaclePool {
+            free: Vec::new(),
+            recycle: true,
+        }
+    }
+}
+
+impl ObstaclePool {
+    /// Spawns and despawns every obstacle, as before pooling. Kept so the
+    /// benchmark can compare the two.
+    pub fn without_recycling() -> Self {
+        ObstaclePool {
+            free: Vec::new(),
+            recycle: false,
+        }
+    }
+
+    /// Obstacles waiting to be reused.
+    pub fn parked(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Puts an obstacle of `kind` on the track at `at`, reusing a parked one
+    /// if there is one.
+    pub fn spawn(&mut self, commands: &mut Commands, kind: ObstacleKind, at: Vec3) {
+        let transform = Transform::from_translation(at);
+        let placed = (SimPosition::new(at), Obstacle, kind);
+        match self.free.pop() {
+            Some(e) => {
+                commands
+                    .entity(e)
+                    .insert((transform, Visibility::Inherited, placed));
+            }
+            None => {
+                commands.spawn((SpatialBundle::from_transform(transform), placed));
+            }
+        }
+    }
+
+    /// Takes `e` off the track.
+    pub fn release(&mut self, commands: &mut Commands, e: Entity) {
+        if !self.recycle {
+            commands.entity(e).despawn_recursive();
+            return;
+        }
+        commands.entity(e).remove::<(Obstacle, Grazed)>().insert((
+            Transform::from_translation(PARKED_AT),
+            Visibility::Hidden,
+            SimPosition::new(PARKED_AT),
+        ));
+        self.free.push(e);
+    }
+}
+
+pub(crate) fn fill_obstacle_pool(mut commands: Commands, mut pool: ResMut<ObstaclePool>) {
+    if !pool.recycle {
+        return;
+    }
+    for _ in 0..POOL_PREALLOC {
+        let e = commands
+            .spawn((
+                SpatialBundle {
+                    transform: Transform::from_translation(PARKED_AT),
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                },
+                SimPosition::new(PARKED_AT),
+                ObstacleKind::default(),
+            ))
+            .id();
+        pool.free.push(e);
+    }
+}
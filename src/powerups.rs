@@ -0,0 +1,234 @@
+//! Power-ups: pickups that ride the track with the obstacles and grant a
+//! timed effect when the player touches one.
+//!
+//! A shield absorbs the next hit, slow motion scales virtual time, a doubler
+//! doubles score gain and a magnet pulls power-ups and coins towards the
+//! player. Effect timers count simulated seconds, so replays match the live
+//! run.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::coins::Coin;
+use crate::collision::{hit_half_extents, swept_overlap};
+use crate::game::{Player, RunRng, Stance, OBSTACLE_START_Z, PLAYER_Z, TRACK_HALF_X};
+use crate::interpolation::SimPosition;
+use crate::lanes::ControlScheme;
+
+pub const POWERUP_SIZE: Vec3 = Vec3::new(0.6, 0.6, 0.6);
+pub const POWERUP_CENTRE_Y: f32 = 0.5; // hovers within reach of every stance
+pub const POWERUP_INTERVAL_SECS: f32 = 9.0; // between power-up spawns
+pub const SHIELD_SECS: f32 = 10.0; // wears off if nothing hits it
+pub const SLOW_MO_SECS: f32 = 4.0; // game seconds; longer on the wall clock
+pub const SLOW_MO_SPEED: f32 = 0.5; // virtual time scale while slowed
+pub const DOUBLER_SECS: f32 = 8.0;
+pub const DOUBLER_FACTOR: f32 = 2.0;
+pub const MAGNET_SECS: f32 = 8.0;
+pub const MAGNET_RANGE_Z: f32 = 12.0; // pulls pickups this far ahead of the player
+pub const MAGNET_PULL: f32 = 6.0; // lateral pull, units/sec
+
+#[derive(Component)]
+pub struct PowerUp;
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PowerUpKind {
+    Shield,
+    SlowMo,
+    Doubler,
+    Magnet,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 4] = [
+        PowerUpKind::Shield,
+        PowerUpKind::SlowMo,
+        PowerUpKind::Doubler,
+        PowerUpKind::Magnet,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PowerUpKind::Shield => "Shield",
+            PowerUpKind::SlowMo => "Slow-mo",
+            PowerUpKind::Doubler => "2x score",
+            PowerUpKind::Magnet => "Magnet",
+        }
+    }
+
+    /// How long the effect lasts from pickup.
+    pub fn secs(self) -> f32 {
+        match self {
+            PowerUpKind::Shield => SHIELD_SECS,
+            PowerUpKind::SlowMo => SLOW_MO_SECS,
+            PowerUpKind::Doubler => DOUBLER_SECS,
+            PowerUpKind::Magnet => MAGNET_SECS,
+        }
+    }
+}
+
+/// Effects running now, with the seconds each has left, oldest first.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct ActiveEffects(Vec<(PowerUpKind, f32)>);
+
+impl ActiveEffects {
+    pub fn iter(&self) -> impl Iterator<Item = (PowerUpKind, f32)> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.remaining(kind).is_some()
+    }
+
+    pub fn remaining(&self, kind: PowerUpKind) -> Option<f32> {
+        self.iter().find(|(k, _)| *k == kind).map(|(_, secs)| secs)
+    }
+
+    /// Starts `kind`, or refills it if already running.
+    pub fn grant(&mut self, kind: PowerUpKind) {
+        match self.0.iter_mut().find(|(k, _)| *k == kind) {
+            Some((_, secs)) => *secs = kind.secs(),
+            None => self.0.push((kind, kind.secs())),
+        }
+    }
+
+    /// Ends `kind` early; false if it wasn't running.
+    pub fn consume(&mut self, kind: PowerUpKind) -> bool {
+        let before = self.0.len();
+        self.0.retain(|(k, _)| *k != kind);
+        self.0.len() != before
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        for (_, secs) in &mut self.0 {
+            *secs -= dt;
+        }
+        self.0.retain(|(_, secs)| *secs > 0.0);
+    }
+
+    /// Multiplier on score gained this tick.
+    pub fn score_factor(&self) -> f32 {
+        if self.is_active(PowerUpKind::Doubler) {
+            DOUBLER_FACTOR
+        } else {
+            1.0
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct PowerUpTimer(pub Timer);
+
+impl Default for PowerUpTimer {
+    fn default() -> Self {
+        PowerUpTimer(Timer::from_seconds(
+            POWERUP_INTERVAL_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+pub(crate) fn reset_powerups(mut effects: ResMut<ActiveEffects>, mut timer: ResMut<PowerUpTimer>) {
+    effects.0.clear();
+    timer.0.reset();
+}
+
+/// One pickup every [`POWERUP_INTERVAL_SECS`], of a random kind, at a random
+/// spot across the track (a lane centre in lane mode).
+pub(crate) fn spawn_powerups(
+    mut commands: Commands,
+    time: Res<Time>,
+    scheme: Res<ControlScheme>,
+    mut timer: ResMut<PowerUpTimer>,
+    mut rng: ResMut<RunRng>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let kind = PowerUpKind::ALL[rng.0.gen_range(0..PowerUpKind::ALL.len())];
+    let x = match scheme.lanes() {
+        Some(lanes) => lanes.centre(rng.0.gen_range(0..lanes.count())),
+        None => rng.0.gen_range(-TRACK_HALF_X..=TRACK_HALF_X),
+    };
+    // Mesh and material are attached by the view plugin.
+    let at = Vec3::new(x, POWERUP_CENTRE_Y, OBSTACLE_START_Z);
+    commands.spawn((
+        SpatialBundle::from_transform(Transform::from_translation(at)),
+        SimPosition::new(at),
+        PowerUp,
+        kind,
+    ));
+}
+
+/// Power-ups and coins: what the magnet pulls.
+type Pickup = (Or<(With<PowerUp>, With<Coin>)>, Without<Player>);
+
+/// While the magnet runs, pickups ahead of the player drift towards its X.
+pub(crate) fn pull_powerups(
+    time: Res<Time>,
+    effects: Res<ActiveEffects>,
+    q_player: Query<&SimPosition, With<Player>>,
+    mut q: Query<&mut SimPosition, Pickup>,
+) {
+    if !effects.is_active(PowerUpKind::Magnet) {
+        return;
+    }
+    let Ok(player) = q_player.get_single() else {
+        return;
+    };
+    let step = MAGNET_PULL * time.delta_seconds();
+    for mut pos in &mut q {
+        if pos.current.z < PLAYER_Z - MAGNET_RANGE_Z || pos.current.z > PLAYER_Z {
+            continue;
+        }
+        let dx = player.current.x - pos.current.x;
+        pos.current.x += dx.clamp(-step, step);
+    }
+}
+
+/// Grants the effect of every pickup the player touched this tick.
+pub(crate) fn collect_powerups(
+    mut commands: Commands,
+    mut effects: ResMut<ActiveEffects>,
+    q_player: Query<(&SimPosition, &Stance), With<Player>>,
+    q: Query<(Entity, &SimPosition, &PowerUpKind), With<PowerUp>>,
+) {
+    let Ok((player, stance)) = q_player.get_single() else {
+        return;
+    };
+    let half = hit_half_extents(stance.size(), POWERUP_SIZE);
+    for (e, pos, kind) in &q {
+        if swept_overlap(player, pos, half) {
+            effects.grant(*kind);
+            commands.entity(e).despawn();
+        }
+    }
+}
+
+/// Counts effects down and keeps virtual time slowed while slow motion runs.
+pub(crate) fn tick_effects(
+    time: Res<Time>,
+    mut effects: ResMut<ActiveEffects>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    effects.tick(time.delta_seconds());
+    let speed = if effects.is_active(PowerUpKind::SlowMo) {
+        SLOW_MO_SPEED
+    } else {
+        1.0
+    };
+    if virtual_time.relative_speed() != speed {
+        virtual_time.set_relative_speed(speed);
+    }
+}
+
+/// Leaving a run clears the track of pickups and restores normal time.
+pub(crate) fn clear_powerups(
+    mut commands: Commands,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    q: Query<Entity, With<PowerUp>>,
+) {
+    for e in &q {
+        commands.entity(e).despawn_recursive();
+    }
+    virtual_time.set_relative_speed(1.0);
+}
@@ -0,0 +1,212 @@
+//! The player's settings and progress (best score, recent runs, name, coin
+//! wallet and every menu setting), persisted across sessions through
+//! [`SaveStore`].
+//!
+//! The profile is loaded while in [`GameState::Loading`], so the menu never
+//! shows before the saved best is known. It is written back after every live
+//! run, unless a save exists that could not be read (or never answered): the
+//! session then plays on defaults and leaves that save alone.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::coins::Coins;
+use crate::devices::{MouseMode, Sensitivity};
+use crate::difficulty::Difficulty;
+use crate::game::{AppBootTime, RunSeed, RunStats, Score};
+use crate::input_map::InputMap;
+use crate::lanes::ControlScheme;
+use crate::motion::TiltSettings;
+use crate::storage::{PendingLoad, SaveStore};
+
+pub const PROFILE_KEY: &str = "profile";
+pub const HISTORY_LEN: usize = 20; // recent runs kept
+const LOAD_TIMEOUT_SECS: f32 = 3.0; // give up waiting on a slow backend
+
+/// Saved form of the profile.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Profile {
+    #[serde(default)]
+    pub best: f32,
+    #[serde(default)]
+    pub history: Vec<RunSummary>,
+    /// Shown on the leaderboard. Empty in saves from before names existed.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    #[serde(default)]
+    pub controls: ControlScheme,
+    #[serde(default)]
+    pub wallet: u64,
+    #[serde(default)]
+    pub bindings: InputMap,
+    #[serde(default)]
+    pub mouse_mode: MouseMode,
+    #[serde(default)]
+    pub sensitivity: Sensitivity,
+    #[serde(default)]
+    pub tilt: TiltSettings,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RunSummary {
+    pub score: f32,
+    pub seed: u64,
+    pub seconds: f32,
+}
+
+/// Recent live runs, newest first.
+#[derive(Resource, Default)]
+pub struct RunHistory(pub Vec<RunSummary>);
+
+/// Name submitted with each run to the leaderboard.
+#[derive(Resource)]
+pub struct PlayerName(pub String);
+
+impl Default for PlayerName {
+    fn default() -> Self {
+        PlayerName("PLAYER".to_string())
+    }
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct ProfileLoad {
+    pending: Option<PendingLoad>,
+    /// Set once the save is known to be readable or absent. Until then,
+    /// saving would overwrite a profile this session never saw.
+    writable: bool,
+}
+
+pub(crate) fn start_profile_load(
+    store: Res<SaveStore>,
+    mut load: ResMut<ProfileLoad>,
+    bt: Res<AppBootTime>,
+) {
+    info!(
+        "[boot] loading profile from {} (+{:?})",
+        store.backend_name(),
+        bt.app_start.elapsed()
+    );
+    load.pending = Some(store.load(PROFILE_KEY));
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn finish_profile_load(
+    time: Res<Time>,
+    mut waited: Local<f32>,
+    mut load: ResMut<ProfileLoad>,
+    mut score: ResMut<Score>,
+    mut history: ResMut<RunHistory>,
+    mut name: ResMut<PlayerName>,
+    mut difficulty: ResMut<Difficulty>,
+    mut controls: ResMut<ControlScheme>,
+    mut coins: ResMut<Coins>,
+    mut bindings: ResMut<InputMap>,
+    mut mouse_mode: ResMut<MouseMode>,
+    mut sensitivity: ResMut<Sensitivity>,
+    mut tilt: ResMut<TiltSettings>,
+) {
+    let Some(pending) = &load.pending else {
+        return;
+    };
+    let loaded = match pending.poll() {
+        Some(Ok(Some(data))) => serde_json::from_str::<Profile>(&data).map_err(|e| {
+            warn!("[save] ignoring unreadable profile: {e}");
+        }),
+        Some(Ok(None)) => Ok(Profile::default()),
+        Some(Err(e)) => {
+            warn!("[save] profile load failed: {e}");
+            Err(())
+        }
+        None => {
+            *waited += time.delta_seconds();
+            if *waited < LOAD_TIMEOUT_SECS {
+                return;
+            }
+            warn!("[save] profile load timed out");
+            Err(())
+        }
+    };
+    load.pending = None;
+    load.writable = loaded.is_ok();
+    if !load.writable {
+        warn!("[save] playing on defaults; the saved profile will not be overwritten");
+    }
+    let profile = loaded.unwrap_or_default();
+    score.best = score.best.max(profile.best);
+    history.0 = profile.history;
+    history.0.truncate(HISTORY_LEN);
+    if !profile.name.is_empty() {
+        name.0 = profile.name;
+    }
+    *difficulty = profile.difficulty;
+    *controls = profile.controls;
+    coins.wallet = profile.wallet;
+    *bindings = profile.bindings;
+    *mouse_mode = profile.mouse_mode;
+    *sensitivity = profile.sensitivity.clamped();
+    *tilt = profile.tilt.clamped();
+}
+
+/// True once the profile has been applied (or given up on).
+pub(crate) fn profile_loaded(load: &ProfileLoad) -> bool {
+    load.pending.is_none()
+}
+
+/// Adds the live run that just ended to the history. [`save_profile`] runs
+/// after it.
+pub(crate) fn record_finished_run(
+    mut score: ResMut<Score>,
+    stats: Res<RunStats>,
+    seed: Res<RunSeed>,
+    mut history: ResMut<RunHistory>,
+) {
+    // A run quit from the pause menu never hit anything, so may be a new best.
+    score.best = score.best.max(score.value);
+    history.0.insert(
+        0,
+        RunSummary {
+            score: score.value,
+            seed: seed.0,
+            seconds: stats.elapsed,
+        },
+    );
+    history.0.truncate(HISTORY_LEN);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn save_profile(
+    score: Res<Score>,
+    history: Res<RunHistory>,
+    name: Res<PlayerName>,
+    difficulty: Res<Difficulty>,
+    controls: Res<ControlScheme>,
+    coins: Res<Coins>,
+    bindings: Res<InputMap>,
+    mouse_mode: Res<MouseMode>,
+    sensitivity: Res<Sensitivity>,
+    tilt: Res<TiltSettings>,
+    store: Res<SaveStore>,
+    load: Res<ProfileLoad>,
+) {
+    if !load.writable {
+        return;
+    }
+    let profile = Profile {
+        best: score.best,
+        history: history.0.clone(),
+        name: name.0.clone(),
+        difficulty: *difficulty,
+        controls: *controls,
+        wallet: coins.wallet,
+        bindings: bindings.clone(),
+        mouse_mode: *mouse_mode,
+        sensitivity: *sensitivity,
+        tilt: *tilt,
+    };
+    match serde_json::to_string(&profile) {
+        Ok(data) => store.store(PROFILE_KEY, data),
+        Err(e) => warn!("[save] could not encode profile: {e}"),
+    }
+}
@@ -0,0 +1,303 @@
+//! Input recording and deterministic replay.
+//!
+//! A run is fully determined by its seed, the difficulty preset and control
+//! scheme it was played under, and the [`Player::target_x`] and
+//! [`Player::action`] that each fixed tick saw, so that is all we record.
+//! [`GameState::Replay`] puts the recorded settings in place for the length
+//! of the replay and feeds the stream back through the same simulation
+//! systems.
+
+use bevy::prelude::*;
+
+use crate::difficulty::Difficulty;
+use crate::game::{GameState, Player, RunSeed, VerticalAction};
+use crate::input_map::{Action, InputMap};
+use crate::lanes::ControlScheme;
+
+/// Leads the current text form; text without it is the original
+/// `seed:runs` form, which predates recorded settings.
+const ENCODING_VERSION: &str = "v2";
+
+/// What one fixed tick read from [`Player`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TickInput {
+    pub target_x: f32,
+    pub action: Option<VerticalAction>,
+}
+
+impl From<f32> for TickInput {
+    fn from(target_x: f32) -> Self {
+        TickInput {
+            target_x,
+            action: None,
+        }
+    }
+}
+
+impl TickInput {
+    fn same_as(&self, other: &TickInput) -> bool {
+        self.target_x.to_bits() == other.target_x.to_bits() && self.action == other.action
+    }
+}
+
+/// Seed and settings plus per-tick input, run-length encoded: steering
+/// input holds the same value for long stretches and jumps are rare, so most
+/// ticks cost nothing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputRecording {
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    pub controls: ControlScheme,
+    runs: Vec<(TickInput, u32)>,
+}
+
+impl InputRecording {
+    /// An empty recording under the default settings.
+    pub fn new(seed: u64) -> Self {
+        InputRecording {
+            seed,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_settings(mut self, difficulty: Difficulty, controls: ControlScheme) -> Self {
+        self.difficulty = difficulty;
+        self.controls = controls;
+        self
+    }
+
+    /// Appends one tick of input.
+    pub fn push(&mut self, input: impl Into<TickInput>) {
+        let input = input.into();
+        match self.runs.last_mut() {
+            Some((last, count)) if last.same_as(&input) => *count += 1,
+            _ => self.runs.push((input, 1)),
+        }
+    }
+
+    /// Number of ticks recorded.
+    pub fn ticks(&self) -> usize {
+        self.runs.iter().map(|(_, n)| *n as usize).sum()
+    }
+
+    /// Per-tick input in order.
+    pub fn iter(&self) -> impl Iterator<Item = TickInput> + '_ {
+        self.runs
+            .iter()
+            .flat_map(|(input, n)| std::iter::repeat_n(*input, *n as usize))
+    }
+
+    /// Text form for bug reports:
+    /// `v2:seed:difficulty:controls:bits*count,bits*count,…` with each
+    /// `target_x` as its exact f32 bit pattern in hex, followed by `j` or `s`
+    /// on ticks that jumped or slid.
+    pub fn encode(&self) -> String {
+        let runs: Vec<String> = self
+            .runs
+            .iter()
+            .map(|(input, n)| {
+                let action = match input.action {
+                    None => "",
+                    Some(VerticalAction::Jump) => "j",
+                    Some(VerticalAction::Slide) => "s",
+                };
+                format!("{:08x}{action}*{n}", input.target_x.to_bits())
+            })
+            .collect();
+        format!(
+            "{ENCODING_VERSION}:{}:{}:{}:{}",
+            self.seed,
+            difficulty_code(self.difficulty),
+            controls_code(self.controls),
+            runs.join(",")
+        )
+    }
+
+    /// Parses the output of [`InputRecording::encode`]. Text from before the
+    /// settings were recorded decodes under the default settings.
+    pub fn decode(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (mut recording, body) = match text.strip_prefix(ENCODING_VERSION) {
+            Some(rest) => {
+                let mut fields = rest.strip_prefix(':')?.splitn(4, ':');
+                let (seed, difficulty, controls, body) = (
+                    fields.next()?,
+                    fields.next()?,
+                    fields.next()?,
+                    fields.next()?,
+                );
+                let difficulty = Difficulty::ALL
+                    .into_iter()
+                    .find(|d| difficulty_code(*d) == difficulty)?;
+                let controls = ControlScheme::ALL
+                    .into_iter()
+                    .find(|c| controls_code(*c) == controls)?;
+                let recording =
+                    InputRecording::new(seed.parse().ok()?).with_settings(difficulty, controls);
+                (recording, body)
+            }
+            None => {
+                let (seed, body) = text.split_once(':')?;
+                (InputRecording::new(seed.parse().ok()?), body)
+            }
+        };
+        for run in body.split(',').filter(|r| !r.is_empty()) {
+            let (input, count) = run.split_once('*')?;
+            let (bits, action) = match input.strip_suffix('j') {
+                Some(bits) => (bits, Some(VerticalAction::Jump)),
+                None => match input.strip_suffix('s') {
+                    Some(bits) => (bits, Some(VerticalAction::Slide)),
+                    None => (input, None),
+                },
+            };
+            let target_x = f32::from_bits(u32::from_str_radix(bits, 16).ok()?);
+            let count: u32 = count.parse().ok()?;
+            if count == 0 {
+                return None;
+            }
+            recording.runs.push((TickInput { target_x, action }, count));
+        }
+        Some(recording)
+    }
+}
+
+fn difficulty_code(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Easy => "easy",
+        Difficulty::Normal => "normal",
+        Difficulty::Hard => "hard",
+    }
+}
+
+fn controls_code(controls: ControlScheme) -> &'static str {
+    match controls {
+        ControlScheme::Analog => "analog",
+        ControlScheme::ThreeLanes => "lanes3",
+        ControlScheme::FiveLanes => "lanes5",
+    }
+}
+
+/// The run currently being recorded.
+#[derive(Resource, Default)]
+pub struct RunRecorder(pub InputRecording);
+
+/// The most recently finished live run, available to replay.
+#[derive(Resource, Default)]
+pub struct LastRun(pub Option<InputRecording>);
+
+/// Playback position within the recording being replayed, and the player's
+/// own settings to put back afterwards.
+#[derive(Resource, Default)]
+pub struct ReplayCursor {
+    recording: InputRecording,
+    run: usize,
+    used: u32,
+    live_settings: Option<(Difficulty, ControlScheme)>,
+}
+
+impl ReplayCursor {
+    fn next(&mut self) -> Option<TickInput> {
+        let (input, count) = *self.recording.runs.get(self.run)?;
+        self.used += 1;
+        if self.used >= count {
+            self.run += 1;
+            self.used = 0;
+        }
+        Some(input)
+    }
+}
+
+pub(crate) fn start_recording(
+    seed: Res<RunSeed>,
+    difficulty: Res<Difficulty>,
+    controls: Res<ControlScheme>,
+    mut recorder: ResMut<RunRecorder>,
+) {
+    recorder.0 = InputRecording::new(seed.0).with_settings(*difficulty, *controls);
+}
+
+/// First step of every live tick: capture what the simulation is about to see.
+pub(crate) fn record_input(q: Query<&Player>, mut recorder: ResMut<RunRecorder>) {
+    if let Ok(p) = q.get_single() {
+        recorder.0.push(TickInput {
+            target_x: p.target_x,
+            action: p.action,
+        });
+    }
+}
+
+pub(crate) fn finish_recording(mut recorder: ResMut<RunRecorder>, mut last: ResMut<LastRun>) {
+    let recording = std::mem::take(&mut recorder.0);
+    info!(
+        "[replay] recorded {} ticks: {}",
+        recording.ticks(),
+        recording.encode()
+    );
+    last.0 = Some(recording);
+}
+
+/// Runs before the run's setup, so the preset and lanes it builds from are
+/// the recorded ones. The swap bypasses change detection: the menu would
+/// otherwise save it to the profile and drop the ghost.
+pub(crate) fn start_replay(
+    last: Res<LastRun>,
+    mut cursor: ResMut<ReplayCursor>,
+    mut difficulty: ResMut<Difficulty>,
+    mut controls: ResMut<ControlScheme>,
+) {
+    let recording = last.0.clone().unwrap_or_default();
+    let live_settings = Some((*difficulty, *controls));
+    *difficulty.bypass_change_detection() = recording.difficulty;
+    *controls.bypass_change_detection() = recording.controls;
+    *cursor = ReplayCursor {
+        recording,
+        live_settings,
+        ..Default::default()
+    };
+}
+
+/// Puts the player's own settings back once the replay ends.
+pub(crate) fn end_replay(
+    mut cursor: ResMut<ReplayCursor>,
+    mut difficulty: ResMut<Difficulty>,
+    mut controls: ResMut<ControlScheme>,
+) {
+    if let Some((live_difficulty, live_controls)) = cursor.live_settings.take() {
+        *difficulty.bypass_change_detection() = live_difficulty;
+        *controls.bypass_change_detection() = live_controls;
+    }
+}
+
+/// First step of every replayed tick: substitute the recorded input. Ends the
+/// replay if the stream runs out before the run does.
+pub(crate) fn feed_replay_input(
+    mut q: Query<&mut Player>,
+    mut cursor: ResMut<ReplayCursor>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(input) = cursor.next() else {
+        next_state.set(GameState::GameOver);
+        return;
+    };
+    for mut p in &mut q {
+        p.target_x = input.target_x;
+        p.action = input.action;
+    }
+}
+
+/// WatchReplay (V) on the game-over screen replays the run that just ended.
+pub(crate) fn game_over_watch_replay(
+    keys: Res<ButtonInput<KeyCode>>,
+    map: Res<InputMap>,
+    last: Res<LastRun>,
+    mut seed: ResMut<RunSeed>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !map.just_pressed(&keys, Action::WatchReplay) {
+        return;
+    }
+    if let Some(recording) = &last.0 {
+        seed.0 = recording.seed;
+        next_state.set(GameState::Replay);
+    }
+}
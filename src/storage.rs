@@ -0,0 +1,189 @@
+//! Small key/value save store with a backend per platform.
+//!
+//! Inside the Tauri webview, saves go through the `load_save`/`store_save`
+//! commands into the app data directory (desktop and Android). Under plain
+//! `trunk serve` they fall back to `localStorage`. Native builds (tests, CI)
+//! keep them in memory.
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::sync::{Arc, Mutex};
+
+pub type LoadResult = Result<Option<String>, String>;
+
+/// Completion slot for a request that may finish asynchronously (e.g. over
+/// Tauri IPC). Systems poll it once per frame.
+pub struct Pending<T>(Arc<Mutex<Option<T>>>);
+
+impl<T> Pending<T> {
+    /// A request that has already finished.
+    pub fn ready(result: T) -> Self {
+        let pending = Pending::default();
+        pending.fulfil(result);
+        pending
+    }
+
+    pub fn fulfil(&self, result: T) {
+        *self.0.lock().unwrap() = Some(result);
+    }
+
+    /// Takes the result if the request has finished.
+    pub fn poll(&self) -> Option<T> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+// Manual impls: deriving would needlessly require `T: Clone + Default`.
+impl<T> Clone for Pending<T> {
+    fn clone(&self) -> Self {
+        Pending(self.0.clone())
+    }
+}
+
+impl<T> Default for Pending<T> {
+    fn default() -> Self {
+        Pending(Arc::new(Mutex::new(None)))
+    }
+}
+
+pub type PendingLoad = Pending<LoadResult>;
+
+pub trait SaveBackend: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+    fn load(&self, key: &str) -> PendingLoad;
+    /// Fire-and-forget; failures are logged by the backend.
+    fn store(&self, key: &str, data: String);
+}
+
+#[derive(Resource)]
+pub struct SaveStore(Box<dyn SaveBackend>);
+
+impl SaveStore {
+    pub fn new(backend: impl SaveBackend) -> Self {
+        SaveStore(Box::new(backend))
+    }
+
+    pub fn backend_name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    pub fn load(&self, key: &str) -> PendingLoad {
+        self.0.load(key)
+    }
+
+    pub fn store(&self, key: &str, data: String) {
+        self.0.store(key, data);
+    }
+}
+
+impl Default for SaveStore {
+    #[cfg(target_arch = "wasm32")]
+    fn default() -> Self {
+        if crate::tauri_ipc::available() {
+            SaveStore::new(web::TauriBackend)
+        } else {
+            SaveStore::new(web::LocalStorageBackend)
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default() -> Self {
+        SaveStore::new(MemoryBackend::default())
+    }
+}
+
+/// In-process store. Clones share the same map, so tests can keep a handle
+/// to seed or inspect what the game saved.
+#[derive(Clone, Default)]
+pub struct MemoryBackend(Arc<Mutex<HashMap<String, String>>>);
+
+impl MemoryBackend {
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.0.lock().unwrap().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: &str, data: impl Into<String>) {
+        self.0.lock().unwrap().insert(key.to_string(), data.into());
+    }
+}
+
+impl SaveBackend for MemoryBackend {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn load(&self, key: &str) -> PendingLoad {
+        PendingLoad::ready(Ok(self.get(key)))
+    }
+
+    fn store(&self, key: &str, data: String) {
+        self.insert(key, data);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use super::{PendingLoad, SaveBackend};
+    use crate::tauri_ipc;
+    use bevy::prelude::*;
+
+    pub struct TauriBackend;
+
+    impl SaveBackend for TauriBackend {
+        fn name(&self) -> &'static str {
+            "tauri"
+        }
+
+        fn load(&self, key: &str) -> PendingLoad {
+            let pending = PendingLoad::default();
+            let reply = pending.clone();
+            let args = serde_json::json!({ "key": key });
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = tauri_ipc::invoke("load_save", &args)
+                    .await
+                    .map(|value| value.as_str().map(str::to_string));
+                reply.fulfil(result);
+            });
+            pending
+        }
+
+        fn store(&self, key: &str, data: String) {
+            let args = serde_json::json!({ "key": key, "data": data });
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(e) = tauri_ipc::invoke("store_save", &args).await {
+                    warn!("[save] store_save failed: {e}");
+                }
+            });
+        }
+    }
+
+    pub struct LocalStorageBackend;
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    impl SaveBackend for LocalStorageBackend {
+        fn name(&self) -> &'static str {
+            "localStorage"
+        }
+
+        fn load(&self, key: &str) -> PendingLoad {
+            let result = match local_storage() {
+                Some(storage) => storage.get_item(key).map_err(|e| format!("{e:?}")),
+                None => Err("localStorage unavailable".to_string()),
+            };
+            PendingLoad::ready(result)
+        }
+
+        fn store(&self, key: &str, data: String) {
+            let Some(storage) = local_storage() else {
+                warn!("[save] localStorage unavailable");
+                return;
+            };
+            if let Err(e) = storage.set_item(key, &data) {
+                warn!("[save] localStorage write failed: {e:?}");
+            }
+        }
+    }
+}
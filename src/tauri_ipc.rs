@@ -0,0 +1,43 @@
+//! Calls into the Tauri backend through the webview's global `__TAURI__`
+//! object (enabled by `app.withGlobalTauri` in `tauri.conf.json`).
+
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// Whether we are running inside the Tauri webview rather than a plain
+/// browser tab (e.g. `trunk serve`).
+pub fn available() -> bool {
+    invoke_fn().is_some()
+}
+
+fn invoke_fn() -> Option<js_sys::Function> {
+    let window = web_sys::window()?;
+    let tauri = js_sys::Reflect::get(&window, &"__TAURI__".into()).ok()?;
+    let core = js_sys::Reflect::get(&tauri, &"core".into()).ok()?;
+    js_sys::Reflect::get(&core, &"invoke".into())
+        .ok()?
+        .dyn_into()
+        .ok()
+}
+
+/// Invokes the command `cmd` with a JSON object of arguments and returns its
+/// JSON result (`null` for commands returning `()` or `None`).
+pub async fn invoke(cmd: &str, args: &serde_json::Value) -> Result<serde_json::Value, String> {
+    let invoke = invoke_fn().ok_or("Tauri IPC unavailable")?;
+    let args = js_sys::JSON::parse(&args.to_string()).map_err(js_error)?;
+    let promise: js_sys::Promise = invoke
+        .call2(&JsValue::NULL, &JsValue::from_str(cmd), &args)
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(js_error)?;
+    let value = JsFuture::from(promise).await.map_err(js_error)?;
+    if value.is_undefined() {
+        return Ok(serde_json::Value::Null);
+    }
+    let text: String = js_sys::JSON::stringify(&value).map_err(js_error)?.into();
+    serde_json::from_str(&text).map_err(|e| e.to_string())
+}
+
+fn js_error(value: JsValue) -> String {
+    value.as_string().unwrap_or_else(|| format!("{value:?}"))
+}
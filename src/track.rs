@@ -0,0 +1,137 @@
+//! Streaming track: a ring of ground segments laid end to end that scroll
+//! towards the player with the obstacles.
+//!
+//! A segment that passes behind the camera jumps to the far end of the ring
+//! and takes the next index, so the track never runs out and no entities
+//! are spawned mid-run. Lane markings and side scenery are drawn by the view
+//! plugin; scenery is picked from the segment index, not [`RunRng`], so it
+//! never changes a run's obstacles.
+//!
+//! [`RunRng`]: crate::game::RunRng
+
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::game::{RunStats, PLAYER_SIZE, TRACK_HALF_X};
+use crate::interpolation::SimPosition;
+use crate::lanes::ControlScheme;
+
+pub const TRACK_SEGMENT_LEN: f32 = 10.0;
+pub const TRACK_SEGMENTS: u32 = 8; // enough to reach past the horizon
+pub const TRACK_RECYCLE_Z: f32 = 25.0; // segments wholly past this are behind the camera
+pub const TRACK_WIDTH: f32 = 10.0;
+pub const SCENERY_PER_SIDE: usize = 2;
+pub const SCENERY_MIN_X: f32 = 6.0; // clear of the ground's edge
+pub const SCENERY_MAX_X: f32 = 9.0;
+pub const SCENERY_MIN_HEIGHT: f32 = 0.5;
+pub const SCENERY_MAX_HEIGHT: f32 = 4.0;
+
+/// How a segment bends and climbs along its length. Drawn as a yaw and
+/// pitch of the segment; every segment is straight and level for now.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SegmentShape {
+    /// Turn from one end to the other, radians; positive bends left.
+    pub curve: f32,
+    /// Rise from the near end to the far end, world units.
+    pub slope: f32,
+}
+
+impl SegmentShape {
+    pub const STRAIGHT: SegmentShape = SegmentShape {
+        curve: 0.0,
+        slope: 0.0,
+    };
+}
+
+/// One piece of ground. Its [`SimPosition`] is the segment's centre.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct TrackSegment {
+    /// Segments laid since the run began, counting this one; picks its scenery.
+    pub index: u32,
+    pub shape: SegmentShape,
+}
+
+/// A prop beside the track, relative to its segment's centre at ground level.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SceneryProp {
+    pub offset: Vec3,
+    pub height: f32,
+}
+
+/// Props for segment `index`, [`SCENERY_PER_SIDE`] on the left then the
+/// same on the right. The same index always gets the same props.
+pub fn scenery(index: u32) -> Vec<SceneryProp> {
+    let mut rng = ChaCha8Rng::seed_from_u64(u64::from(index));
+    let half_len = TRACK_SEGMENT_LEN * 0.5;
+    let mut props = Vec::with_capacity(2 * SCENERY_PER_SIDE);
+    for side in [-1.0, 1.0] {
+        for _ in 0..SCENERY_PER_SIDE {
+            let x = side * rng.gen_range(SCENERY_MIN_X..SCENERY_MAX_X);
+            let z = rng.gen_range(-half_len..half_len);
+            props.push(SceneryProp {
+                offset: Vec3::new(x, 0.0, z),
+                height: rng.gen_range(SCENERY_MIN_HEIGHT..SCENERY_MAX_HEIGHT),
+            });
+        }
+    }
+    props
+}
+
+/// X of each painted line along the track: the two edges the player can
+/// reach first, then the lines between lanes in lane mode.
+pub fn lane_markings(scheme: ControlScheme) -> Vec<f32> {
+    let edge = TRACK_HALF_X + PLAYER_SIZE.x * 0.5;
+    let mut xs = vec![-edge, edge];
+    if let Some(lanes) = scheme.lanes() {
+        xs.extend((1..lanes.count()).map(|i| -TRACK_HALF_X + i as f32 * lanes.width()));
+    }
+    xs
+}
+
+/// Centre of segment `index` when the run starts, the first one ending at
+/// [`TRACK_RECYCLE_Z`].
+fn start_z(index: u32) -> f32 {
+    TRACK_RECYCLE_Z - TRACK_SEGMENT_LEN * (index as f32 + 0.5)
+}
+
+pub(crate) fn spawn_track(mut commands: Commands) {
+    // Meshes, lane markings and scenery are attached by the view plugin.
+    for index in 0..TRACK_SEGMENTS {
+        let at = Vec3::new(0.0, 0.0, start_z(index));
+        commands.spawn((
+            SpatialBundle::from_transform(Transform::from_translation(at)),
+            SimPosition::new(at),
+            TrackSegment {
+                index,
+                shape: SegmentShape::STRAIGHT,
+            },
+        ));
+    }
+}
+
+/// Moves the ground at obstacle speed and sends segments that have passed
+/// behind the camera to the far end.
+pub(crate) fn scroll_track(
+    time: Res<Time>,
+    stats: Res<RunStats>,
+    mut q: Query<(&mut SimPosition, &mut TrackSegment)>,
+) {
+    let ring = TRACK_SEGMENT_LEN * TRACK_SEGMENTS as f32;
+    for (mut pos, mut segment) in &mut q {
+        pos.current.z += stats.speed * time.delta_seconds();
+        if pos.current.z - TRACK_SEGMENT_LEN * 0.5 > TRACK_RECYCLE_Z {
+            // Shift both ends so interpolation doesn't sweep across the track.
+            pos.current.z -= ring;
+            pos.previous.z -= ring;
+            segment.index += TRACK_SEGMENTS;
+            segment.shape = SegmentShape::STRAIGHT;
+        }
+    }
+}
+
+pub(crate) fn clear_track(mut commands: Commands, q: Query<Entity, With<TrackSegment>>) {
+    for e in &q {
+        commands.entity(e).despawn_recursive();
+    }
+}
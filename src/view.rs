@@ -0,0 +1,1601 @@
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::prelude::*;
+#[cfg(target_os = "android")]
+use bevy::render::camera::PerspectiveProjection;
+use bevy::text::JustifyText;
+use bevy::ui::{AlignSelf, JustifyContent, ZIndex};
+
+use crate::coins::{Coin, Coins, COIN_SIZE};
+use crate::combo::Combo;
+use crate::devices::{MouseMode, Sensitivity, SensitivityDevice};
+use crate::difficulty::{Difficulty, DifficultyProfile};
+use crate::game::{
+    AppBootTime, GameState, InRun, Obstacle, ObstacleKind, OrientationState, Player, RunSeed,
+    RunStats, Score, Stance, PLAYER_SIZE,
+};
+use crate::ghost::{BestGhost, Ghost, GhostRecorder};
+use crate::input_map::{Action, InputMap, Rebinding};
+use crate::lanes::ControlScheme;
+use crate::leaderboard::{format_date, Leaderboard};
+use crate::motion::{TiltAngle, TiltSettings, TiltState};
+use crate::pause::PauseState;
+use crate::powerups::{ActiveEffects, PowerUp, PowerUpKind, POWERUP_SIZE};
+use crate::profile::{PlayerName, RunHistory};
+use crate::track::{
+    lane_markings, scenery, SceneryProp, SegmentShape, TrackSegment, TRACK_SEGMENT_LEN, TRACK_WIDTH,
+};
+
+const MENU_RECENT_RUNS: usize = 5;
+const COIN_COLOR: Color = Color::srgb(1.0, 0.8, 0.2);
+const MARKING_WIDTH: f32 = 0.08;
+const SCENERY_WIDTH: f32 = 0.6;
+
+#[cfg(target_os = "android")]
+fn camera_bundle() -> Camera3dBundle {
+    Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 12.0, 22.0)
+            .looking_at(Vec3::new(0.0, 0.5, 0.0), Vec3::Y),
+        projection: PerspectiveProjection {
+            fov: 80_f32.to_radians(),
+            ..Default::default()
+        }
+        .into(),
+        camera: Camera {
+            hdr: false,
+            ..Default::default()
+        },
+        tonemapping: Tonemapping::None,
+        ..Default::default()
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+fn camera_bundle() -> Camera3dBundle {
+    Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 6.0, 8.0).looking_at(Vec3::new(0.0, 0.5, 0.0), Vec3::Y),
+        camera: Camera {
+            hdr: false,
+            ..Default::default()
+        },
+        tonemapping: Tonemapping::None,
+        ..Default::default()
+    }
+}
+
+#[derive(Component)]
+struct Warmup;
+
+/// A line painted on a [`TrackSegment`].
+#[derive(Component)]
+struct LaneMarking;
+
+/// Slot in a segment's [`scenery`] list; moved when the segment is recycled.
+#[derive(Component)]
+struct Scenery(usize);
+
+#[derive(Clone)]
+pub struct ObstacleLook {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+#[derive(Resource, Clone)]
+pub struct ObstacleAssets {
+    pub block: ObstacleLook,
+    pub hurdle: ObstacleLook,
+    pub barrier: ObstacleLook,
+}
+
+impl ObstacleAssets {
+    pub fn look(&self, kind: ObstacleKind) -> &ObstacleLook {
+        match kind {
+            ObstacleKind::Block => &self.block,
+            ObstacleKind::Hurdle => &self.hurdle,
+            ObstacleKind::Barrier => &self.barrier,
+        }
+    }
+}
+
+#[derive(Resource, Clone)]
+pub struct CoinAssets(pub ObstacleLook);
+
+#[derive(Resource, Clone)]
+pub struct TrackAssets {
+    pub ground: ObstacleLook,
+    /// Full segment length; edge lines.
+    pub line: ObstacleLook,
+    /// Half a segment; lines between lanes, so they read as passing dashes.
+    pub dash: ObstacleLook,
+    /// Unit cube, scaled to each prop.
+    pub scenery: ObstacleLook,
+}
+
+#[derive(Resource, Clone)]
+pub struct PowerUpAssets {
+    pub mesh: Handle<Mesh>,
+    pub shield: Handle<StandardMaterial>,
+    pub slow_mo: Handle<StandardMaterial>,
+    pub doubler: Handle<StandardMaterial>,
+    pub magnet: Handle<StandardMaterial>,
+}
+
+impl PowerUpAssets {
+    pub fn material(&self, kind: PowerUpKind) -> &Handle<StandardMaterial> {
+        match kind {
+            PowerUpKind::Shield => &self.shield,
+            PowerUpKind::SlowMo => &self.slow_mo,
+            PowerUpKind::Doubler => &self.doubler,
+            PowerUpKind::Magnet => &self.magnet,
+        }
+    }
+}
+
+fn powerup_color(kind: PowerUpKind) -> Color {
+    match kind {
+        PowerUpKind::Shield => Color::srgb(0.3, 0.9, 1.0),
+        PowerUpKind::SlowMo => Color::srgb(0.4, 0.5, 1.0),
+        PowerUpKind::Doubler => Color::srgb(1.0, 0.85, 0.1),
+        PowerUpKind::Magnet => Color::srgb(1.0, 0.3, 0.8),
+    }
+}
+
+#[derive(Component)]
+struct ScoreText;
+#[derive(Component)]
+struct CoinText;
+#[derive(Component)]
+struct ComboText;
+#[derive(Component)]
+struct GhostText;
+#[derive(Component)]
+struct MenuUi;
+#[derive(Component)]
+struct SeedText;
+#[derive(Component)]
+struct GameOverUi;
+#[derive(Component)]
+struct HudRoot;
+/// One HUD row per power-up, hidden while that effect is off.
+#[derive(Component)]
+struct EffectRow(PowerUpKind);
+/// The countdown bar inside an [`EffectRow`].
+#[derive(Component)]
+struct EffectFill(PowerUpKind);
+#[derive(Component)]
+struct LeaderboardButton;
+#[derive(Component)]
+struct DifficultyButton;
+#[derive(Component)]
+struct DifficultyLabel;
+#[derive(Component)]
+struct ControlsButton;
+#[derive(Component)]
+struct ControlsLabel;
+#[derive(Component)]
+struct MouseModeButton;
+#[derive(Component)]
+struct MouseModeLabel;
+#[derive(Component)]
+struct SensitivityButton;
+#[derive(Component)]
+struct SensitivityLabel;
+/// Moves the selected device's sensitivity this many steps.
+#[derive(Component)]
+struct SensitivityStepButton(i32);
+#[derive(Component)]
+struct TiltButton;
+#[derive(Component)]
+struct TiltLabel;
+#[derive(Component)]
+struct TiltAngleButton;
+#[derive(Component)]
+struct TiltAngleLabel;
+/// Moves the selected tilt angle this many steps.
+#[derive(Component)]
+struct TiltStepButton(i32);
+#[derive(Component)]
+struct BindingsButton;
+#[derive(Component)]
+struct LeaderboardUi;
+#[derive(Component)]
+struct LeaderboardText;
+#[derive(Component)]
+struct BindingsUi;
+#[derive(Component)]
+struct BindingsText;
+#[derive(Component)]
+struct BindingsBackButton;
+#[derive(Component)]
+struct PauseUi;
+#[derive(Component)]
+enum PauseButton {
+    Resume,
+    Quit,
+}
+
+#[derive(Component)]
+struct OrientationOverlay;
+
+/// Camera, meshes and UI for [`crate::game::GamePlugin`]. Requires the
+/// rendering and UI plugins from `DefaultPlugins`.
+pub struct GameViewPlugin;
+
+impl Plugin for GameViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup)
+            // Menu
+            .add_systems(OnEnter(GameState::Menu), enter_menu)
+            .add_systems(
+                Update,
+                (
+                    update_seed_text,
+                    update_difficulty_label,
+                    update_controls_label,
+                    update_mouse_mode_label,
+                    update_sensitivity_label,
+                    update_tilt_label,
+                    update_tilt_angle_label,
+                    press_leaderboard_button,
+                    press_bindings_button,
+                    press_difficulty_button,
+                    press_controls_button,
+                    press_mouse_mode_button,
+                    press_sensitivity_buttons,
+                    press_tilt_button,
+                    press_tilt_angle_buttons,
+                )
+                    .run_if(in_state(GameState::Menu)),
+            )
+            .add_systems(OnExit(GameState::Menu), exit_menu)
+            // Leaderboard
+            .add_systems(OnEnter(GameState::Leaderboard), enter_leaderboard)
+            .add_systems(
+                Update,
+                update_leaderboard_text.run_if(in_state(GameState::Leaderboard)),
+            )
+            .add_systems(OnExit(GameState::Leaderboard), exit_leaderboard)
+            // Bindings
+            .add_systems(OnEnter(GameState::Bindings), enter_bindings_screen)
+            .add_systems(
+                Update,
+                (update_bindings_text, press_bindings_back_button)
+                    .run_if(in_state(GameState::Bindings)),
+            )
+            .add_systems(OnExit(GameState::Bindings), exit_bindings_screen)
+            // Playing / Replay
+            .add_systems(OnEnter(InRun), enter_playing)
+            .add_systems(OnEnter(GameState::Replay), enter_replay)
+            .add_systems(
+                Update,
+                (
+                    attach_player_mesh,
+                    attach_track_meshes,
+                    shape_track_segments,
+                    attach_obstacle_mesh,
+                    attach_powerup_mesh,
+                    attach_coin_mesh,
+                    attach_ghost_mesh,
+                    show_stance,
+                    update_score_text,
+                    update_coin_text,
+                    update_combo_text,
+                    update_ghost_text,
+                    update_effect_bars,
+                )
+                    .run_if(in_state(InRun)),
+            )
+            .add_systems(OnExit(InRun), exit_playing)
+            // Paused
+            .add_systems(OnEnter(PauseState::Paused), enter_paused)
+            .add_systems(
+                Update,
+                press_pause_buttons.run_if(in_state(PauseState::Paused)),
+            )
+            .add_systems(OnExit(PauseState::Paused), exit_paused)
+            // GameOver
+            .add_systems(OnEnter(GameState::GameOver), enter_game_over)
+            .add_systems(OnExit(GameState::GameOver), exit_game_over)
+            .add_systems(Update, manage_orientation_overlay);
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    bt: Res<AppBootTime>,
+) {
+    info!(
+        "[boot] setup: begin (+{:?} since start)",
+        bt.app_start.elapsed()
+    );
+    // Camera slightly above and behind, looking at the play area
+    commands.spawn(camera_bundle());
+
+    // Prewarm PBR pipeline with an off-screen unlit cube
+    let warm_mesh = meshes.add(Mesh::from(Cuboid::new(0.1, 0.1, 0.1)));
+    let warm_mat = materials.add(StandardMaterial {
+        base_color: Color::srgb(1.0, 1.0, 1.0),
+        unlit: true,
+        ..Default::default()
+    });
+    commands.spawn((
+        PbrBundle {
+            mesh: warm_mesh,
+            material: warm_mat,
+            transform: Transform::from_xyz(0.0, -1000.0, 0.0),
+            ..Default::default()
+        },
+        Warmup,
+    ));
+
+    info!("[boot] setup: end (+{:?})", bt.app_start.elapsed());
+}
+
+// --- Menu ---
+#[allow(clippy::too_many_arguments)]
+fn enter_menu(
+    mut commands: Commands,
+    seed: Res<RunSeed>,
+    score: Res<Score>,
+    history: Res<RunHistory>,
+    difficulty: Res<Difficulty>,
+    controls: Res<ControlScheme>,
+    mouse_mode: Res<MouseMode>,
+    sensitivity: Res<Sensitivity>,
+    sensitivity_device: Res<SensitivityDevice>,
+    tilt: Res<TiltSettings>,
+    tilt_angle: Res<TiltAngle>,
+    coins: Res<Coins>,
+) {
+    let recent: Vec<String> = history
+        .0
+        .iter()
+        .take(MENU_RECENT_RUNS)
+        .map(|run| (run.score as i32).to_string())
+        .collect();
+    let mut record = format!("Best: {}  Coins: {}", score.best as i32, coins.wallet);
+    if !recent.is_empty() {
+        record.push_str(&format!("  Recent: {}", recent.join(", ")));
+    }
+
+    // Full-screen centered "Tap to Start"
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(16.0),
+                    ..Default::default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
+                ..Default::default()
+            },
+            MenuUi,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new(
+                        "Tap to Start\n",
+                        TextStyle {
+                            font_size: 42.0,
+                            color: Color::WHITE,
+                            ..Default::default()
+                        },
+                    ),
+                    TextSection::new(
+                        format!("Seed: {}\n", seed.0),
+                        TextStyle {
+                            font_size: 24.0,
+                            color: Color::WHITE,
+                            ..Default::default()
+                        },
+                    ),
+                    TextSection::new(
+                        "Type digits to set seed, R for random\n",
+                        TextStyle {
+                            font_size: 18.0,
+                            color: Color::srgb(0.7, 0.7, 0.7),
+                            ..Default::default()
+                        },
+                    ),
+                    TextSection::new(
+                        record,
+                        TextStyle {
+                            font_size: 20.0,
+                            color: Color::WHITE,
+                            ..Default::default()
+                        },
+                    ),
+                ])
+                .with_text_justify(JustifyText::Center),
+                SeedText,
+            ));
+            parent
+                .spawn((menu_button_bundle(), DifficultyButton))
+                .with_children(|button| {
+                    button.spawn((
+                        TextBundle::from_section(
+                            difficulty_label(*difficulty),
+                            menu_button_text_style(),
+                        ),
+                        DifficultyLabel,
+                    ));
+                });
+            parent
+                .spawn((menu_button_bundle(), ControlsButton))
+                .with_children(|button| {
+                    button.spawn((
+                        TextBundle::from_section(
+                            controls_label(*controls),
+                            menu_button_text_style(),
+                        ),
+                        ControlsLabel,
+                    ));
+                });
+            parent
+                .spawn((menu_button_bundle(), MouseModeButton))
+                .with_children(|button| {
+                    button.spawn((
+                        TextBundle::from_section(
+                            mouse_mode_label(*mouse_mode),
+                            menu_button_text_style(),
+                        ),
+                        MouseModeLabel,
+                    ));
+                });
+            parent.spawn(menu_row_bundle()).with_children(|row| {
+                row.spawn((menu_button_bundle(), SensitivityStepButton(-1)))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section("-", menu_button_text_style()));
+                    });
+                row.spawn((menu_button_bundle(), SensitivityButton))
+                    .with_children(|button| {
+                        button.spawn((
+                            TextBundle::from_section(
+                                sensitivity_label(*sensitivity, *sensitivity_device),
+                                menu_button_text_style(),
+                            ),
+                            SensitivityLabel,
+                        ));
+                    });
+                row.spawn((menu_button_bundle(), SensitivityStepButton(1)))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section("+", menu_button_text_style()));
+                    });
+            });
+            parent
+                .spawn((menu_button_bundle(), TiltButton))
+                .with_children(|button| {
+                    button.spawn((
+                        TextBundle::from_section(tilt_label(*tilt), menu_button_text_style()),
+                        TiltLabel,
+                    ));
+                });
+            parent.spawn(menu_row_bundle()).with_children(|row| {
+                row.spawn((menu_button_bundle(), TiltStepButton(-1)))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section("-", menu_button_text_style()));
+                    });
+                row.spawn((menu_button_bundle(), TiltAngleButton))
+                    .with_children(|button| {
+                        button.spawn((
+                            TextBundle::from_section(
+                                tilt_angle_label(*tilt, *tilt_angle),
+                                menu_button_text_style(),
+                            ),
+                            TiltAngleLabel,
+                        ));
+                    });
+                row.spawn((menu_button_bundle(), TiltStepButton(1)))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section("+", menu_button_text_style()));
+                    });
+            });
+            parent
+                .spawn((menu_button_bundle(), LeaderboardButton))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(
+                        "Leaderboard (L)",
+                        menu_button_text_style(),
+                    ));
+                });
+            parent
+                .spawn((menu_button_bundle(), BindingsButton))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(
+                        "Key bindings (K)",
+                        menu_button_text_style(),
+                    ));
+                });
+        });
+}
+
+fn menu_button_bundle() -> ButtonBundle {
+    ButtonBundle {
+        style: Style {
+            padding: UiRect::axes(Val::Px(18.0), Val::Px(8.0)),
+            ..Default::default()
+        },
+        background_color: BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.15)),
+        ..Default::default()
+    }
+}
+
+/// Lays buttons side by side, e.g. a setting between its - and +.
+fn menu_row_bundle() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(8.0),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn menu_button_text_style() -> TextStyle {
+    TextStyle {
+        font_size: 22.0,
+        color: Color::WHITE,
+        ..Default::default()
+    }
+}
+
+fn difficulty_label(difficulty: Difficulty) -> String {
+    format!("Difficulty: {} (D)", difficulty.label())
+}
+
+fn update_difficulty_label(
+    difficulty: Res<Difficulty>,
+    mut q: Query<&mut Text, With<DifficultyLabel>>,
+) {
+    if !difficulty.is_changed() {
+        return;
+    }
+    for mut text in &mut q {
+        text.sections[0].value = difficulty_label(*difficulty);
+    }
+}
+
+fn press_difficulty_button(
+    q: Query<&Interaction, (Changed<Interaction>, With<DifficultyButton>)>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    if q.iter().any(|i| *i == Interaction::Pressed) {
+        *difficulty = difficulty.next();
+    }
+}
+
+fn controls_label(controls: ControlScheme) -> String {
+    format!("Controls: {} (C)", controls.label())
+}
+
+fn update_controls_label(
+    controls: Res<ControlScheme>,
+    mut q: Query<&mut Text, With<ControlsLabel>>,
+) {
+    if !controls.is_changed() {
+        return;
+    }
+    for mut text in &mut q {
+        text.sections[0].value = controls_label(*controls);
+    }
+}
+
+fn press_controls_button(
+    q: Query<&Interaction, (Changed<Interaction>, With<ControlsButton>)>,
+    mut controls: ResMut<ControlScheme>,
+) {
+    if q.iter().any(|i| *i == Interaction::Pressed) {
+        *controls = controls.next();
+    }
+}
+
+fn mouse_mode_label(mode: MouseMode) -> String {
+    format!("Mouse: {} (M)", mode.label())
+}
+
+fn update_mouse_mode_label(mode: Res<MouseMode>, mut q: Query<&mut Text, With<MouseModeLabel>>) {
+    if !mode.is_changed() {
+        return;
+    }
+    for mut text in &mut q {
+        text.sections[0].value = mouse_mode_label(*mode);
+    }
+}
+
+fn press_mouse_mode_button(
+    q: Query<&Interaction, (Changed<Interaction>, With<MouseModeButton>)>,
+    mut mode: ResMut<MouseMode>,
+) {
+    if q.iter().any(|i| *i == Interaction::Pressed) {
+        *mode = mode.next();
+    }
+}
+
+fn sensitivity_label(sensitivity: Sensitivity, device: SensitivityDevice) -> String {
+    format!(
+        "{} sensitivity: {:.2}x (S, -/+)",
+        device.label(),
+        sensitivity.multiple(device)
+    )
+}
+
+fn update_sensitivity_label(
+    sensitivity: Res<Sensitivity>,
+    device: Res<SensitivityDevice>,
+    mut q: Query<&mut Text, With<SensitivityLabel>>,
+) {
+    if !sensitivity.is_changed() && !device.is_changed() {
+        return;
+    }
+    for mut text in &mut q {
+        text.sections[0].value = sensitivity_label(*sensitivity, *device);
+    }
+}
+
+/// The middle button picks the device, like S; the sides step it, like -/+.
+fn press_sensitivity_buttons(
+    q_device: Query<&Interaction, (Changed<Interaction>, With<SensitivityButton>)>,
+    q_step: Query<(&Interaction, &SensitivityStepButton), Changed<Interaction>>,
+    mut device: ResMut<SensitivityDevice>,
+    mut sensitivity: ResMut<Sensitivity>,
+) {
+    if q_device.iter().any(|i| *i == Interaction::Pressed) {
+        *device = device.next();
+    }
+    for (interaction, step) in &q_step {
+        if *interaction == Interaction::Pressed {
+            sensitivity.adjust(*device, step.0);
+        }
+    }
+}
+
+fn tilt_label(tilt: TiltSettings) -> String {
+    let on = if tilt.enabled { "On" } else { "Off" };
+    format!("Tilt steering: {on} (T)")
+}
+
+fn update_tilt_label(tilt: Res<TiltSettings>, mut q: Query<&mut Text, With<TiltLabel>>) {
+    if !tilt.is_changed() {
+        return;
+    }
+    for mut text in &mut q {
+        text.sections[0].value = tilt_label(*tilt);
+    }
+}
+
+/// Turning tilt on takes the current hold as level, like T does.
+fn press_tilt_button(
+    q: Query<&Interaction, (Changed<Interaction>, With<TiltButton>)>,
+    mut tilt: ResMut<TiltSettings>,
+    mut state: ResMut<TiltState>,
+) {
+    if q.iter().any(|i| *i == Interaction::Pressed) {
+        tilt.enabled = !tilt.enabled;
+        state.calibrating = tilt.enabled;
+    }
+}
+
+fn tilt_angle_label(tilt: TiltSettings, angle: TiltAngle) -> String {
+    format!(
+        "{}: {:.0} deg (Z, [/])",
+        angle.label(),
+        tilt.angle(angle).to_degrees()
+    )
+}
+
+fn update_tilt_angle_label(
+    tilt: Res<TiltSettings>,
+    angle: Res<TiltAngle>,
+    mut q: Query<&mut Text, With<TiltAngleLabel>>,
+) {
+    if !tilt.is_changed() && !angle.is_changed() {
+        return;
+    }
+    for mut text in &mut q {
+        text.sections[0].value = tilt_angle_label(*tilt, *angle);
+    }
+}
+
+/// The middle button picks the angle, like Z; the sides step it, like [/].
+fn press_tilt_angle_buttons(
+    q_angle: Query<&Interaction, (Changed<Interaction>, With<TiltAngleButton>)>,
+    q_step: Query<(&Interaction, &TiltStepButton), Changed<Interaction>>,
+    mut angle: ResMut<TiltAngle>,
+    mut tilt: ResMut<TiltSettings>,
+) {
+    if q_angle.iter().any(|i| *i == Interaction::Pressed) {
+        *angle = angle.next();
+    }
+    for (interaction, step) in &q_step {
+        if *interaction == Interaction::Pressed {
+            tilt.adjust(*angle, step.0);
+        }
+    }
+}
+
+fn press_leaderboard_button(
+    q: Query<&Interaction, (Changed<Interaction>, With<LeaderboardButton>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if q.iter().any(|i| *i == Interaction::Pressed) {
+        next_state.set(GameState::Leaderboard);
+    }
+}
+
+fn press_bindings_button(
+    q: Query<&Interaction, (Changed<Interaction>, With<BindingsButton>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if q.iter().any(|i| *i == Interaction::Pressed) {
+        next_state.set(GameState::Bindings);
+    }
+}
+
+fn update_seed_text(seed: Res<RunSeed>, mut q: Query<&mut Text, With<SeedText>>) {
+    if !seed.is_changed() {
+        return;
+    }
+    for mut text in &mut q {
+        text.sections[1].value = format!("Seed: {}\n", seed.0);
+    }
+}
+
+fn exit_menu(mut commands: Commands, q: Query<Entity, With<MenuUi>>, bt: Res<AppBootTime>) {
+    info!("[boot] menu: exit (+{:?})", bt.app_start.elapsed());
+    for e in &q {
+        commands.entity(e).despawn_recursive();
+    }
+}
+
+// --- Leaderboard ---
+fn enter_leaderboard(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                ..Default::default()
+            },
+            LeaderboardUi,
+        ))
+        .with_children(|parent| {
+            let style = |font_size, color| TextStyle {
+                font_size,
+                color,
+                ..Default::default()
+            };
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new("Leaderboard\n", style(40.0, Color::WHITE)),
+                    TextSection::new(String::new(), style(24.0, Color::WHITE)),
+                    TextSection::new(String::new(), style(22.0, Color::WHITE)),
+                    TextSection::new(
+                        "Type to change name  -  Tap or Esc to go back",
+                        style(18.0, Color::srgb(0.7, 0.7, 0.7)),
+                    ),
+                ])
+                .with_text_justify(JustifyText::Center),
+                LeaderboardText,
+            ));
+        });
+}
+
+fn update_leaderboard_text(
+    board: Res<Leaderboard>,
+    name: Res<PlayerName>,
+    mut q: Query<&mut Text, With<LeaderboardText>>,
+) {
+    if !board.is_changed() && !name.is_changed() {
+        return;
+    }
+    let rows = if board.loading {
+        "Loading...".to_string()
+    } else if let Some(e) = &board.error {
+        format!("Could not load leaderboard: {e}")
+    } else if board.entries.is_empty() {
+        "No runs yet".to_string()
+    } else {
+        board
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                format!(
+                    "{:>2}. {:<12} {:>6}  {:>5.1}s  {}  seed {}",
+                    i + 1,
+                    entry.name,
+                    entry.score as i32,
+                    entry.duration_secs,
+                    format_date(entry.date),
+                    entry.seed
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    for mut text in &mut q {
+        text.sections[1].value = format!("Name: {}\n\n", name.0);
+        text.sections[2].value = format!("{rows}\n\n");
+    }
+}
+
+fn exit_leaderboard(mut commands: Commands, q: Query<Entity, With<LeaderboardUi>>) {
+    for e in &q {
+        commands.entity(e).despawn_recursive();
+    }
+}
+
+// --- Bindings ---
+fn enter_bindings_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(16.0),
+                    ..Default::default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                ..Default::default()
+            },
+            BindingsUi,
+        ))
+        .with_children(|parent| {
+            let style = |font_size, color| TextStyle {
+                font_size,
+                color,
+                ..Default::default()
+            };
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new("Key bindings\n", style(40.0, Color::WHITE)),
+                    TextSection::new(String::new(), style(22.0, Color::WHITE)),
+                    TextSection::new(
+                        "Up/Down: choose  Enter: add key  Backspace: clear  R: defaults  Esc: back\n\
+                         Only the actions above can be rebound; menu and leaderboard keys are fixed",
+                        style(18.0, Color::srgb(0.7, 0.7, 0.7)),
+                    ),
+                ])
+                .with_text_justify(JustifyText::Center),
+                BindingsText,
+            ));
+            parent
+                .spawn((menu_button_bundle(), BindingsBackButton))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section("Back", menu_button_text_style()));
+                });
+        });
+}
+
+fn update_bindings_text(
+    map: Res<InputMap>,
+    rebinding: Res<Rebinding>,
+    mut q: Query<&mut Text, With<BindingsText>>,
+) {
+    if !map.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+    let rows = Action::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let keys = if i == rebinding.selected && rebinding.listening {
+                "press a key...".to_string()
+            } else {
+                map.describe(*action)
+            };
+            let cursor = if i == rebinding.selected { ">" } else { " " };
+            format!("{cursor} {:<14} {keys}", action.label())
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    for mut text in &mut q {
+        text.sections[1].value = format!("\n{rows}\n\n");
+    }
+}
+
+fn press_bindings_back_button(
+    q: Query<&Interaction, (Changed<Interaction>, With<BindingsBackButton>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if q.iter().any(|i| *i == Interaction::Pressed) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn exit_bindings_screen(mut commands: Commands, q: Query<Entity, With<BindingsUi>>) {
+    for e in &q {
+        commands.entity(e).despawn_recursive();
+    }
+}
+
+// --- Playing ---
+fn enter_playing(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    score: Res<Score>,
+    profile: Res<DifficultyProfile>,
+) {
+    // Cache track and obstacle meshes/materials so spawns reuse GPU resources.
+    let mut look = |size: Vec3, color: Color| ObstacleLook {
+        mesh: meshes.add(Mesh::from(Cuboid::new(size.x, size.y, size.z))),
+        material: materials.add(StandardMaterial {
+            base_color: color,
+            unlit: true,
+            ..Default::default()
+        }),
+    };
+    let marking = Color::srgb(0.5, 0.5, 0.6);
+    commands.insert_resource(TrackAssets {
+        ground: look(
+            Vec3::new(TRACK_WIDTH, 0.1, TRACK_SEGMENT_LEN),
+            Color::srgb(0.12, 0.12, 0.16),
+        ),
+        line: look(Vec3::new(MARKING_WIDTH, 0.01, TRACK_SEGMENT_LEN), marking),
+        dash: look(
+            Vec3::new(MARKING_WIDTH, 0.01, TRACK_SEGMENT_LEN * 0.5),
+            marking,
+        ),
+        scenery: look(Vec3::ONE, Color::srgb(0.25, 0.3, 0.4)),
+    });
+    commands.insert_resource(ObstacleAssets {
+        block: look(ObstacleKind::Block.size(), Color::srgb(1.0, 0.3, 0.3)),
+        hurdle: look(ObstacleKind::Hurdle.size(), Color::srgb(1.0, 0.7, 0.2)),
+        barrier: look(ObstacleKind::Barrier.size(), Color::srgb(0.7, 0.3, 1.0)),
+    });
+    commands.insert_resource(CoinAssets(ObstacleLook {
+        mesh: meshes.add(Mesh::from(Cylinder::new(COIN_SIZE.x * 0.5, COIN_SIZE.z))),
+        material: materials.add(StandardMaterial {
+            base_color: COIN_COLOR,
+            unlit: true,
+            ..Default::default()
+        }),
+    }));
+    let mut material = |kind: PowerUpKind| {
+        materials.add(StandardMaterial {
+            base_color: powerup_color(kind),
+            unlit: true,
+            ..Default::default()
+        })
+    };
+    commands.insert_resource(PowerUpAssets {
+        mesh: meshes.add(Mesh::from(Sphere::new(POWERUP_SIZE.x * 0.5))),
+        shield: material(PowerUpKind::Shield),
+        slow_mo: material(PowerUpKind::SlowMo),
+        doubler: material(PowerUpKind::Doubler),
+        magnet: material(PowerUpKind::Magnet),
+    });
+
+    // HUD (score)
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(16.0),
+                    left: Val::Px(16.0),
+                    flex_direction: FlexDirection::Column,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            HudRoot,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        column_gap: Val::Px(24.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|row| {
+                    row.spawn((
+                        TextBundle::from_section(
+                            format!(
+                                "Score: 0  Best: {}  Speed: {:.1}  0 m",
+                                score.best as i32, profile.obstacle_speed
+                            ),
+                            TextStyle {
+                                font_size: 28.0,
+                                color: Color::WHITE,
+                                ..Default::default()
+                            },
+                        ),
+                        ScoreText,
+                    ));
+                    row.spawn((
+                        TextBundle::from_section(
+                            "Coins: 0",
+                            TextStyle {
+                                font_size: 28.0,
+                                color: COIN_COLOR,
+                                ..Default::default()
+                            },
+                        ),
+                        CoinText,
+                    ));
+                });
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 24.0,
+                        color: Color::srgb(1.0, 0.5, 0.3),
+                        ..Default::default()
+                    },
+                ),
+                ComboText,
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 22.0,
+                        color: Color::srgba(0.7, 0.85, 1.0, 0.9),
+                        ..Default::default()
+                    },
+                ),
+                GhostText,
+            ));
+            for kind in PowerUpKind::ALL {
+                spawn_effect_row(parent, kind);
+            }
+        });
+}
+
+/// Label and countdown bar for one power-up effect.
+fn spawn_effect_row(parent: &mut ChildBuilder, kind: PowerUpKind) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(8.0),
+                    margin: UiRect::top(Val::Px(4.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            EffectRow(kind),
+        ))
+        .with_children(|row| {
+            row.spawn(TextBundle::from_section(
+                kind.label(),
+                TextStyle {
+                    font_size: 18.0,
+                    color: powerup_color(kind),
+                    ..Default::default()
+                },
+            ));
+            row.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(120.0),
+                    height: Val::Px(8.0),
+                    ..Default::default()
+                },
+                background_color: BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.2)),
+                ..Default::default()
+            })
+            .with_children(|track| {
+                track.spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..Default::default()
+                        },
+                        background_color: BackgroundColor(powerup_color(kind)),
+                        ..Default::default()
+                    },
+                    EffectFill(kind),
+                ));
+            });
+        });
+}
+
+fn enter_replay(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(16.0),
+                    right: Val::Px(16.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            HudRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "REPLAY",
+                TextStyle {
+                    font_size: 28.0,
+                    color: Color::srgb(1.0, 0.8, 0.2),
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+fn attach_player_mesh(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    q: Query<Entity, Added<Player>>,
+) {
+    for e in &q {
+        let player_mesh = meshes.add(Mesh::from(Cuboid::new(
+            PLAYER_SIZE.x,
+            PLAYER_SIZE.y,
+            PLAYER_SIZE.z,
+        )));
+        let player_mat = materials.add(StandardMaterial {
+            base_color: Color::srgb(0.2, 0.9, 0.3),
+            unlit: true,
+            ..Default::default()
+        });
+        commands.entity(e).insert((player_mesh, player_mat));
+    }
+}
+
+/// Ground, painted lines and scenery for each new segment. The segment's
+/// own transform is its centre on the ground surface.
+fn attach_track_meshes(
+    mut commands: Commands,
+    assets: Res<TrackAssets>,
+    scheme: Res<ControlScheme>,
+    q: Query<(Entity, &TrackSegment), Added<TrackSegment>>,
+) {
+    let markings = lane_markings(*scheme);
+    for (e, segment) in &q {
+        let edges = markings.len().min(2);
+        commands.entity(e).with_children(|parent| {
+            parent.spawn(PbrBundle {
+                mesh: assets.ground.mesh.clone(),
+                material: assets.ground.material.clone(),
+                transform: Transform::from_xyz(0.0, -0.05, 0.0),
+                ..Default::default()
+            });
+            for (i, x) in markings.iter().enumerate() {
+                let look = if i < edges {
+                    &assets.line
+                } else {
+                    &assets.dash
+                };
+                parent.spawn((
+                    PbrBundle {
+                        mesh: look.mesh.clone(),
+                        material: look.material.clone(),
+                        transform: Transform::from_xyz(*x, 0.005, 0.0),
+                        ..Default::default()
+                    },
+                    LaneMarking,
+                ));
+            }
+            for (slot, prop) in scenery(segment.index).into_iter().enumerate() {
+                parent.spawn((
+                    PbrBundle {
+                        mesh: assets.scenery.mesh.clone(),
+                        material: assets.scenery.material.clone(),
+                        transform: scenery_transform(prop),
+                        ..Default::default()
+                    },
+                    Scenery(slot),
+                ));
+            }
+        });
+    }
+}
+
+fn scenery_transform(prop: SceneryProp) -> Transform {
+    Transform::from_translation(prop.offset + Vec3::Y * prop.height * 0.5).with_scale(Vec3::new(
+        SCENERY_WIDTH,
+        prop.height,
+        SCENERY_WIDTH,
+    ))
+}
+
+fn shape_rotation(shape: SegmentShape) -> Quat {
+    let pitch = (shape.slope / TRACK_SEGMENT_LEN).atan();
+    Quat::from_euler(EulerRot::YXZ, shape.curve, pitch, 0.0)
+}
+
+/// Re-dresses a segment that has been sent to the far end with a new index.
+fn shape_track_segments(
+    mut q_segments: Query<(&TrackSegment, &Children, &mut Transform), Changed<TrackSegment>>,
+    mut q_scenery: Query<(&Scenery, &mut Transform), Without<TrackSegment>>,
+) {
+    for (segment, children, mut t) in &mut q_segments {
+        t.rotation = shape_rotation(segment.shape);
+        let props = scenery(segment.index);
+        for child in children.iter() {
+            if let Ok((slot, mut t)) = q_scenery.get_mut(*child) {
+                *t = scenery_transform(props[slot.0]);
+            }
+        }
+    }
+}
+
+fn attach_obstacle_mesh(
+    mut commands: Commands,
+    obstacle_assets: Res<ObstacleAssets>,
+    q: Query<(Entity, &ObstacleKind), Added<Obstacle>>,
+) {
+    for (e, kind) in &q {
+        let look = obstacle_assets.look(*kind);
+        commands
+            .entity(e)
+            .insert((look.mesh.clone(), look.material.clone()));
+    }
+}
+
+fn attach_powerup_mesh(
+    mut commands: Commands,
+    assets: Res<PowerUpAssets>,
+    q: Query<(Entity, &PowerUpKind), Added<PowerUp>>,
+) {
+    for (e, kind) in &q {
+        commands
+            .entity(e)
+            .insert((assets.mesh.clone(), assets.material(*kind).clone()));
+    }
+}
+
+fn attach_coin_mesh(
+    mut commands: Commands,
+    assets: Res<CoinAssets>,
+    mut q: Query<(Entity, &mut Transform), Added<Coin>>,
+) {
+    for (e, mut t) in &mut q {
+        // Stand the disc up to face the camera.
+        t.rotation = Quat::from_rotation_x(std::f32::consts::FRAC_PI_2);
+        commands
+            .entity(e)
+            .insert((assets.0.mesh.clone(), assets.0.material.clone()));
+    }
+}
+
+/// Squashes the player while sliding; the jump itself is in its position.
+fn show_stance(mut q: Query<(&Stance, &mut Transform), Changed<Stance>>) {
+    for (stance, mut t) in &mut q {
+        t.scale.y = stance.size().y / PLAYER_SIZE.y;
+    }
+}
+
+fn attach_ghost_mesh(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    q: Query<Entity, Added<Ghost>>,
+) {
+    for e in &q {
+        let ghost_mesh = meshes.add(Mesh::from(Cuboid::new(
+            PLAYER_SIZE.x,
+            PLAYER_SIZE.y,
+            PLAYER_SIZE.z,
+        )));
+        let ghost_mat = materials.add(StandardMaterial {
+            base_color: Color::srgba(0.6, 0.8, 1.0, 0.35),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..Default::default()
+        });
+        commands.entity(e).insert((ghost_mesh, ghost_mat));
+    }
+}
+
+fn update_ghost_text(
+    recorder: Res<GhostRecorder>,
+    best: Res<BestGhost>,
+    mut q: Query<&mut Text, With<GhostText>>,
+) {
+    if !recorder.is_changed() {
+        return;
+    }
+    let Some(gap) = recorder.gap(&best) else {
+        return;
+    };
+    let label = if gap < 0.0 {
+        format!("Ghost: {:.0} m behind", -gap)
+    } else {
+        format!("Ghost: {:.0} m ahead", gap)
+    };
+    for mut text in &mut q {
+        text.sections[0].value.clone_from(&label);
+    }
+}
+
+fn update_score_text(
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    mut q: Query<&mut Text, With<ScoreText>>,
+    orientation: Res<OrientationState>,
+) {
+    if !score.is_changed() && !stats.is_changed() {
+        return;
+    }
+    if orientation.is_portrait {
+        return;
+    }
+    for mut text in &mut q {
+        text.sections[0].value = format!(
+            "Score: {}  Best: {}  Speed: {:.1}  {:.0} m",
+            score.value as i32, score.best as i32, stats.speed, stats.distance
+        );
+    }
+}
+
+fn update_coin_text(coins: Res<Coins>, mut q: Query<&mut Text, With<CoinText>>) {
+    if !coins.is_changed() {
+        return;
+    }
+    for mut text in &mut q {
+        text.sections[0].value = format!("Coins: {}", coins.run);
+    }
+}
+
+fn update_combo_text(combo: Res<Combo>, mut q: Query<&mut Text, With<ComboText>>) {
+    if !combo.is_changed() {
+        return;
+    }
+    let label = if combo.multiplier > 1.0 {
+        format!("Combo x{:.1}", combo.multiplier)
+    } else {
+        String::new()
+    };
+    for mut text in &mut q {
+        text.sections[0].value.clone_from(&label);
+    }
+}
+
+fn update_effect_bars(
+    effects: Res<ActiveEffects>,
+    mut q_rows: Query<(&EffectRow, &mut Style), Without<EffectFill>>,
+    mut q_fills: Query<(&EffectFill, &mut Style), Without<EffectRow>>,
+) {
+    if !effects.is_changed() {
+        return;
+    }
+    for (row, mut style) in &mut q_rows {
+        style.display = if effects.is_active(row.0) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    for (fill, mut style) in &mut q_fills {
+        let left = effects.remaining(fill.0).unwrap_or(0.0) / fill.0.secs();
+        style.width = Val::Percent(100.0 * left.clamp(0.0, 1.0));
+    }
+}
+
+fn exit_playing(mut commands: Commands, q_hud: Query<Entity, With<HudRoot>>) {
+    for e in &q_hud {
+        commands.entity(e).despawn_recursive();
+    }
+    commands.remove_resource::<TrackAssets>();
+    commands.remove_resource::<ObstacleAssets>();
+    commands.remove_resource::<PowerUpAssets>();
+    commands.remove_resource::<CoinAssets>();
+}
+
+// --- Paused ---
+fn enter_paused(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(16.0),
+                    ..Default::default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                z_index: ZIndex::Global(10),
+                ..Default::default()
+            },
+            PauseUi,
+        ))
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    "Paused\nEsc/P/Start: resume  Q/Select: quit",
+                    TextStyle {
+                        font_size: 36.0,
+                        color: Color::WHITE,
+                        ..Default::default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+            );
+            for (label, button) in [("Resume", PauseButton::Resume), ("Quit", PauseButton::Quit)] {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(180.0),
+                                padding: UiRect::vertical(Val::Px(10.0)),
+                                justify_content: JustifyContent::Center,
+                                ..Default::default()
+                            },
+                            background_color: BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.15)),
+                            ..Default::default()
+                        },
+                        button,
+                    ))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section(
+                            label,
+                            TextStyle {
+                                font_size: 26.0,
+                                color: Color::WHITE,
+                                ..Default::default()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+fn press_pause_buttons(
+    q: Query<(&Interaction, &PauseButton), Changed<Interaction>>,
+    mut next_pause: ResMut<NextState<PauseState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, button) in &q {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            PauseButton::Resume => next_pause.set(PauseState::Running),
+            PauseButton::Quit => next_state.set(GameState::Menu),
+        }
+    }
+}
+
+fn exit_paused(mut commands: Commands, q: Query<Entity, With<PauseUi>>) {
+    for e in &q {
+        commands.entity(e).despawn_recursive();
+    }
+}
+
+// --- Game Over ---
+fn enter_game_over(
+    mut commands: Commands,
+    score: Res<Score>,
+    combo: Res<Combo>,
+    coins: Res<Coins>,
+    stats: Res<RunStats>,
+    seed: Res<RunSeed>,
+    map: Res<InputMap>,
+) {
+    let msg = format!(
+        "Game Over\nScore: {}  Best: {}\nDistance: {:.0} m in {:.1}s  Dodged: {}\nRun: {}  Near misses: {} (+{})  Best combo: x{:.1}\nCoins: +{}  Wallet: {}\nSeed: {}\nTap to Restart  (Hold or {}: replay seed, {}: watch replay)",
+        score.value as i32,
+        score.best as i32,
+        stats.distance,
+        stats.elapsed,
+        stats.dodged,
+        (score.value - combo.bonus) as i32,
+        combo.near_misses,
+        combo.bonus as i32,
+        combo.best_multiplier,
+        coins.run,
+        coins.wallet,
+        seed.0,
+        map.describe(Action::Retry),
+        map.describe(Action::WatchReplay)
+    );
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
+                ..Default::default()
+            },
+            GameOverUi,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                msg,
+                TextStyle {
+                    font_size: 36.0,
+                    color: Color::WHITE,
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+fn exit_game_over(mut commands: Commands, q: Query<Entity, With<GameOverUi>>) {
+    for e in &q {
+        commands.entity(e).despawn_recursive();
+    }
+}
+
+fn manage_orientation_overlay(
+    orientation: Res<OrientationState>,
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<OrientationOverlay>>,
+) {
+    if orientation.is_portrait {
+        if overlay_query.is_empty() {
+            commands
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            top: Val::Px(0.0),
+                            left: Val::Px(0.0),
+                            position_type: PositionType::Absolute,
+                            ..Default::default()
+                        },
+                        background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 1.0)),
+                        z_index: ZIndex::Global(100),
+                        ..Default::default()
+                    },
+                    OrientationOverlay,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section(
+                            "Rotate device to landscape",
+                            TextStyle {
+                                font_size: 36.0,
+                                color: Color::WHITE,
+                                ..Default::default()
+                            },
+                        )
+                        .with_justify(JustifyText::Center),
+                        style: Style {
+                            align_self: AlignSelf::Center,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    });
+                });
+        }
+    } else {
+        for entity in &overlay_query {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! Authored obstacle formations ("waves").
+//!
+//! Each spawn picks a [`WavePattern`] by weight from those unlocked at the
+//! current tier, lays it out as `(x, depth)` offsets and spawns one
+//! [`Obstacle`](crate::game::Obstacle) of the pattern's [`ObstacleKind`] per
+//! offset. The tier rises with time survived, starting from the difficulty
+//! preset's `start_tier`. All randomness comes from the run's RNG, so replays
+//! see the same waves. In lane mode the same patterns are laid out on lane
+//! centres instead.
+
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::game::{ObstacleKind, TRACK_HALF_X};
+use crate::lanes::Lanes;
+
+pub const TIER_SECS: f32 = 20.0; // survival time per tier step
+pub const MAX_TIER: u32 = 3;
+pub const WALL_SPACING: f32 = 1.2; // cube centres in a wall row; too tight to slip through
+pub const WALL_GAP: f32 = 2.4; // opening left in a wall, between cube centres
+pub const ZIGZAG_STEPS: usize = 5;
+pub const ZIGZAG_DEPTH_STEP: f32 = 4.0;
+pub const STAGGER_DEPTH: f32 = 5.0;
+pub const CORRIDOR_ROWS: usize = 4;
+pub const CORRIDOR_DEPTH_STEP: f32 = 3.0;
+pub const CORRIDOR_START_GAP: f32 = 6.4; // opening of the first corridor row
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WavePattern {
+    /// One cube anywhere on the track.
+    Single,
+    /// A row across the track with one opening.
+    WallWithGap,
+    /// Cubes alternating either side of a line, one after another.
+    ZigZag,
+    /// One cube in each half of the track, one behind the other.
+    StaggeredPair,
+    /// Wall rows whose opening narrows towards a fixed centre.
+    ClosingCorridor,
+    /// Low hurdles across the whole track.
+    HurdleRow,
+    /// Overhead barriers across the whole track.
+    OverheadBar,
+}
+
+pub struct PatternSpec {
+    pub pattern: WavePattern,
+    pub weight: u32,
+    /// First tier the pattern can appear at.
+    pub min_tier: u32,
+}
+
+pub const PATTERNS: &[PatternSpec] = &[
+    PatternSpec {
+        pattern: WavePattern::Single,
+        weight: 4,
+        min_tier: 0,
+    },
+    PatternSpec {
+        pattern: WavePattern::StaggeredPair,
+        weight: 3,
+        min_tier: 0,
+    },
+    PatternSpec {
+        pattern: WavePattern::ZigZag,
+        weight: 2,
+        min_tier: 1,
+    },
+    PatternSpec {
+        pattern: WavePattern::WallWithGap,
+        weight: 2,
+        min_tier: 1,
+    },
+    PatternSpec {
+        pattern: WavePattern::ClosingCorridor,
+        weight: 1,
+        min_tier: 2,
+    },
+    PatternSpec {
+        pattern: WavePattern::HurdleRow,
+        weight: 2,
+        min_tier: 0,
+    },
+    PatternSpec {
+        pattern: WavePattern::OverheadBar,
+        weight: 2,
+        min_tier: 1,
+    },
+];
+
+/// Seconds added to the next spawn interval so a deep formation has passed
+/// the spawn line before the next one starts.
+#[derive(Resource, Default)]
+pub struct WaveCooldown(pub f32);
+
+/// Pattern tier after `elapsed_secs` of a run.
+pub fn tier(elapsed_secs: f32, start_tier: u32) -> u32 {
+    (start_tier + (elapsed_secs / TIER_SECS) as u32).min(MAX_TIER)
+}
+
+/// Picks a pattern unlocked at `tier`, by weight.
+pub fn pick(rng: &mut impl Rng, tier: u32) -> WavePattern {
+    let unlocked: Vec<&PatternSpec> = PATTERNS.iter().filter(|p| p.min_tier <= tier).collect();
+    unlocked
+        .choose_weighted(rng, |p| p.weight)
+        .map(|p| p.pattern)
+        .unwrap_or(WavePattern::Single)
+}
+
+impl WavePattern {
+    pub fn kind(self) -> ObstacleKind {
+        match self {
+            WavePattern::HurdleRow => ObstacleKind::Hurdle,
+            WavePattern::OverheadBar => ObstacleKind::Barrier,
+            _ => ObstacleKind::Block,
+        }
+    }
+
+    /// Obstacle offsets as `(x, depth)`: `x` on the track, `depth` how far
+    /// behind the spawn line.
+    pub fn layout(self, rng: &mut impl Rng) -> Vec<Vec2> {
+        match self {
+            WavePattern::Single => {
+                vec![Vec2::new(rng.gen_range(-TRACK_HALF_X..=TRACK_HALF_X), 0.0)]
+            }
+            WavePattern::WallWithGap => {
+                let centre = gap_centre(rng, WALL_GAP);
+                wall_row(centre, WALL_GAP, 0.0)
+            }
+            WavePattern::ZigZag => {
+                let amplitude = rng.gen_range(1.5..=3.0);
+                let centre = rng.gen_range(-TRACK_HALF_X + amplitude..=TRACK_HALF_X - amplitude);
+                let first_side = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+                (0..ZIGZAG_STEPS)
+                    .map(|i| {
+                        let side = if i % 2 == 0 { first_side } else { -first_side };
+                        Vec2::new(centre + side * amplitude, i as f32 * ZIGZAG_DEPTH_STEP)
+                    })
+                    .collect()
+            }
+            WavePattern::StaggeredPair => {
+                let left = rng.gen_range(-TRACK_HALF_X..=-1.0);
+                let right = rng.gen_range(1.0..=TRACK_HALF_X);
+                let (first, second) = if rng.gen_bool(0.5) {
+                    (left, right)
+                } else {
+                    (right, left)
+                };
+                vec![Vec2::new(first, 0.0), Vec2::new(second, STAGGER_DEPTH)]
+            }
+            WavePattern::ClosingCorridor => {
+                let centre = gap_centre(rng, WALL_GAP);
+                (0..CORRIDOR_ROWS)
+                    .flat_map(|row| {
+                        let t = row as f32 / (CORRIDOR_ROWS - 1) as f32;
+                        let gap = CORRIDOR_START_GAP + (WALL_GAP - CORRIDOR_START_GAP) * t;
+                        wall_row(centre, gap, row as f32 * CORRIDOR_DEPTH_STEP)
+                    })
+                    .collect()
+            }
+            WavePattern::HurdleRow | WavePattern::OverheadBar => wall_row(0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Like [`WavePattern::layout`], with every block on a lane centre.
+    /// Hurdle and barrier rows still span the whole track.
+    pub fn lane_layout(self, rng: &mut impl Rng, lanes: Lanes) -> Vec<Vec2> {
+        let n = lanes.count();
+        let at = |lane: usize, depth: f32| Vec2::new(lanes.centre(lane), depth);
+        match self {
+            WavePattern::Single => vec![at(rng.gen_range(0..n), 0.0)],
+            WavePattern::WallWithGap => {
+                let gap = rng.gen_range(0..n);
+                lane_row(lanes, gap, gap, 0.0)
+            }
+            WavePattern::ZigZag => {
+                let left = rng.gen_range(0..n - 1);
+                let flip = rng.gen_bool(0.5);
+                (0..ZIGZAG_STEPS)
+                    .map(|i| {
+                        let lane = if (i % 2 == 0) != flip { left } else { left + 1 };
+                        at(lane, i as f32 * ZIGZAG_DEPTH_STEP)
+                    })
+                    .collect()
+            }
+            WavePattern::StaggeredPair => {
+                let left = rng.gen_range(0..n / 2);
+                let right = rng.gen_range(n - n / 2..n);
+                let (first, second) = if rng.gen_bool(0.5) {
+                    (left, right)
+                } else {
+                    (right, left)
+                };
+                vec![at(first, 0.0), at(second, STAGGER_DEPTH)]
+            }
+            WavePattern::ClosingCorridor => {
+                // Three lanes open, then just the centre one.
+                let centre = rng.gen_range(0..n);
+                (0..CORRIDOR_ROWS)
+                    .flat_map(|row| {
+                        let half = usize::from(row < CORRIDOR_ROWS / 2);
+                        let open_from = centre.saturating_sub(half);
+                        let open_to = (centre + half).min(n - 1);
+                        lane_row(lanes, open_from, open_to, row as f32 * CORRIDOR_DEPTH_STEP)
+                    })
+                    .collect()
+            }
+            WavePattern::HurdleRow | WavePattern::OverheadBar => self.layout(rng),
+        }
+    }
+}
+
+/// A block on every lane centre at `depth`, except lanes `open_from..=open_to`.
+fn lane_row(lanes: Lanes, open_from: usize, open_to: usize, depth: f32) -> Vec<Vec2> {
+    (0..lanes.count())
+        .filter(|lane| !(open_from..=open_to).contains(lane))
+        .map(|lane| Vec2::new(lanes.centre(lane), depth))
+        .collect()
+}
+
+/// A centre for an opening of width `gap` that lies fully on the track.
+fn gap_centre(rng: &mut impl Rng, gap: f32) -> f32 {
+    let half = gap * 0.5;
+    rng.gen_range(-TRACK_HALF_X + half..=TRACK_HALF_X - half)
+}
+
+/// Cubes across the whole track at `depth`, except within the opening.
+fn wall_row(centre: f32, gap: f32, depth: f32) -> Vec<Vec2> {
+    let count = (2.0 * TRACK_HALF_X / WALL_SPACING).round() as usize;
+    (0..=count)
+        .map(|i| -TRACK_HALF_X + i as f32 * (2.0 * TRACK_HALF_X / count as f32))
+        .filter(|x| (x - centre).abs() >= gap * 0.5)
+        .map(|x| Vec2::new(x, depth))
+        .collect()
+}
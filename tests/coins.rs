@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use taurc_ui::coins::{arc, Coin, Coins, COIN_SIZE};
+use taurc_ui::collision::{hit_half_extents, swept_overlap};
+use taurc_ui::game::{
+    Obstacle, ObstacleKind, Player, RunSeed, GRAVITY, JUMP_SPEED, OBSTACLE_START_Z, PLAYER_SIZE,
+};
+use taurc_ui::headless::{
+    advance, boot, booted_app, headless_app, run_until_game_over, started_app, tap_key,
+    DEFAULT_FRAME_STEP,
+};
+use taurc_ui::interpolation::SimPosition;
+use taurc_ui::profile::{Profile, PROFILE_KEY};
+use taurc_ui::storage::MemoryBackend;
+use taurc_ui::GameState;
+
+fn coin_ahead(app: &mut App, y: f32) {
+    app.world_mut().spawn((
+        SimPosition::new(Vec3::new(0.0, y, -4.0)),
+        Transform::default(),
+        Coin,
+    ));
+}
+
+#[test]
+fn arcs_top_out_within_a_jump() {
+    let apex = PLAYER_SIZE.y * 0.5 + JUMP_SPEED * JUMP_SPEED / (2.0 * GRAVITY);
+    let reach = hit_half_extents(PLAYER_SIZE, COIN_SIZE).y;
+    let coins = arc(1.0);
+    let top = coins.iter().map(|c| c.y).fold(f32::MIN, f32::max);
+    assert!(top < apex + reach, "top coin at {top}");
+    assert!(top > PLAYER_SIZE.y * 0.5 + reach, "top coin needs no jump");
+    for c in &coins {
+        assert_eq!(c.x, 1.0);
+        assert!(c.z > OBSTACLE_START_Z);
+    }
+}
+
+#[test]
+fn coins_are_banked_and_saved_after_a_live_run() {
+    let store = MemoryBackend::default();
+    let mut app = started_app(3, &store);
+    // Two at a runner's height, one too high to reach without jumping.
+    coin_ahead(&mut app, 0.4);
+    coin_ahead(&mut app, 2.5);
+    advance(&mut app, 30);
+    assert_eq!(app.world().resource::<Coins>().run, 1);
+    run_until_game_over(&mut app);
+
+    let coins = app.world().resource::<Coins>();
+    assert!(coins.run >= 1);
+    assert_eq!(coins.wallet, u64::from(coins.run));
+    let saved: Profile = serde_json::from_str(&store.get(PROFILE_KEY).unwrap()).unwrap();
+    assert_eq!(saved.wallet, coins.wallet);
+
+    let next_session = booted_app(3, &store);
+    assert_eq!(
+        next_session.world().resource::<Coins>().wallet,
+        saved.wallet
+    );
+}
+
+/// Coins seen inside an obstacle, and coins seen at all.
+#[derive(Resource, Default)]
+struct Overlaps {
+    buried: usize,
+    coins: usize,
+}
+
+fn find_buried(
+    mut seen: ResMut<Overlaps>,
+    q_coins: Query<&SimPosition, With<Coin>>,
+    q_obstacles: Query<(&SimPosition, &ObstacleKind), With<Obstacle>>,
+) {
+    for coin in &q_coins {
+        seen.coins += 1;
+        let buried = q_obstacles
+            .iter()
+            .any(|(op, kind)| swept_overlap(coin, op, hit_half_extents(COIN_SIZE, kind.size())));
+        seen.buried += usize::from(buried);
+    }
+}
+
+#[test]
+fn coins_stay_clear_of_obstacles() {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(RunSeed(11))
+        .init_resource::<Overlaps>()
+        .add_systems(
+            FixedPostUpdate,
+            find_buried.run_if(in_state(GameState::Playing)),
+        );
+    boot(&mut app);
+    tap_key(&mut app, KeyCode::Space);
+    let world = app.world_mut();
+    let player = world.query_filtered::<Entity, With<Player>>().single(world);
+    world.despawn(player);
+    advance(&mut app, 40 * 60);
+
+    let seen = app.world().resource::<Overlaps>();
+    assert!(seen.coins > 0);
+    assert_eq!(seen.buried, 0);
+}
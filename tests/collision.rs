@@ -0,0 +1,216 @@
+use bevy::prelude::*;
+use taurc_ui::collision::{hit_half_extents, swept_overlap};
+use taurc_ui::game::{
+    Obstacle, ObstacleKind, Player, RunStats, Stance, OBSTACLE_SIZE, PLAYER_SIZE,
+};
+use taurc_ui::headless::{advance, boot, headless_app, state, tap_key, DEFAULT_FRAME_STEP};
+use taurc_ui::interpolation::SimPosition;
+use taurc_ui::GameState;
+
+const SPEEDS: [f32; 6] = [1.0, 20.0, 80.0, 400.0, 5_000.0, 1.0e6];
+const DELTAS: [f32; 6] = [1.0 / 480.0, 1.0 / 144.0, 1.0 / 60.0, 1.0 / 20.0, 0.25, 1.0];
+
+fn half() -> Vec3 {
+    hit_half_extents(PLAYER_SIZE, OBSTACLE_SIZE)
+}
+
+fn moved(from: Vec3, to: Vec3) -> SimPosition {
+    SimPosition {
+        previous: from,
+        current: to,
+    }
+}
+
+/// Steps an obstacle towards a stationary player at `speed` for ticks of
+/// `dt`, starting at `phase` (0..1) of a tick so the crossing lands anywhere
+/// inside a step. Returns whether any tick reported a hit.
+fn passes_and_hits(x: f32, speed: f32, dt: f32, phase: f32) -> bool {
+    let player = SimPosition::new(Vec3::ZERO);
+    let step = speed * dt;
+    let mut z = -25.0 - step * phase;
+    while z < 7.0 {
+        let next = z + step;
+        if swept_overlap(
+            &player,
+            &moved(Vec3::new(x, 0.0, z), Vec3::new(x, 0.0, next)),
+            half(),
+        ) {
+            return true;
+        }
+        z = next;
+    }
+    false
+}
+
+#[test]
+fn static_overlap_matches_point_check() {
+    let player = SimPosition::new(Vec3::ZERO);
+    let h = half();
+    assert!(swept_overlap(&player, &SimPosition::new(Vec3::ZERO), h));
+    assert!(swept_overlap(
+        &player,
+        &SimPosition::new(Vec3::new(h.x * 0.99, h.y * 0.99, h.z * 0.99)),
+        h
+    ));
+    assert!(!swept_overlap(
+        &player,
+        &SimPosition::new(Vec3::new(h.x, 0.0, 0.0)),
+        h
+    ));
+    assert!(!swept_overlap(
+        &player,
+        &SimPosition::new(Vec3::new(0.0, 0.0, h.z)),
+        h
+    ));
+    assert!(!swept_overlap(
+        &player,
+        &SimPosition::new(Vec3::new(0.0, h.y, 0.0)),
+        h
+    ));
+}
+
+#[test]
+fn tunnelling_obstacle_is_caught() {
+    let player = SimPosition::new(Vec3::ZERO);
+    // Both endpoints are clear of the player; the path between is not.
+    let obstacle = moved(Vec3::new(0.0, 0.0, -10.0), Vec3::new(0.0, 0.0, 10.0));
+    assert!(swept_overlap(&player, &obstacle, half()));
+}
+
+#[test]
+fn hits_are_never_skipped_at_any_speed_or_delta() {
+    let h = half();
+    for speed in SPEEDS {
+        for dt in DELTAS {
+            for phase in [0.0, 0.13, 0.5, 0.77, 0.999] {
+                for x in [0.0, h.x * 0.5, -h.x * 0.9, h.x * 0.999] {
+                    assert!(
+                        passes_and_hits(x, speed, dt, phase),
+                        "missed hit at x={x} speed={speed} dt={dt} phase={phase}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn clear_lanes_never_hit_at_any_speed_or_delta() {
+    let h = half();
+    for speed in SPEEDS {
+        for dt in DELTAS {
+            for phase in [0.0, 0.5, 0.999] {
+                for x in [h.x, -h.x * 1.01, 3.0] {
+                    assert!(
+                        !passes_and_hits(x, speed, dt, phase),
+                        "false hit at x={x} speed={speed} dt={dt} phase={phase}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn player_sliding_into_passing_obstacle_is_caught() {
+    // The player dodges sideways into the obstacle's lane while it passes
+    // straight through: neither endpoint overlaps, the crossing does.
+    let player = moved(Vec3::new(-2.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0));
+    let obstacle = moved(Vec3::new(0.0, 0.0, -2.0), Vec3::new(0.0, 0.0, 2.0));
+    assert!(swept_overlap(&player, &obstacle, half()));
+}
+
+#[test]
+fn game_over_when_obstacle_crosses_player_within_one_tick() {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    boot(&mut app);
+    tap_key(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Playing);
+
+    // A run this long drives obstacles ~80 units per tick: far enough to
+    // start behind the player and finish past the despawn line.
+    app.world_mut().resource_mut::<RunStats>().elapsed = 10_000.0;
+    let player_x = app
+        .world_mut()
+        .query_filtered::<&SimPosition, With<Player>>()
+        .single(app.world())
+        .current
+        .x;
+    app.world_mut().spawn((
+        SimPosition::new(Vec3::new(player_x, 0.4, -1.0)),
+        Transform::default(),
+        Obstacle,
+        ObstacleKind::Block,
+    ));
+    advance(&mut app, 3);
+    assert_eq!(state(&app), GameState::GameOver);
+}
+
+#[test]
+fn heights_decide_which_stance_clears_each_kind() {
+    let clears = |stance: Stance, kind: ObstacleKind| {
+        let player = SimPosition::new(Vec3::new(0.0, stance.centre_y(), 0.0));
+        let obstacle = SimPosition::new(Vec3::new(0.0, kind.centre_y(), 0.0));
+        !swept_overlap(
+            &player,
+            &obstacle,
+            hit_half_extents(stance.size(), kind.size()),
+        )
+    };
+    let airborne = Stance::Jumping(0.3);
+    let sliding = Stance::Sliding(0.1);
+    for kind in [
+        ObstacleKind::Block,
+        ObstacleKind::Hurdle,
+        ObstacleKind::Barrier,
+    ] {
+        assert!(!clears(Stance::Running, kind), "{kind:?} must hit a runner");
+    }
+    assert!(clears(airborne, ObstacleKind::Hurdle));
+    assert!(!clears(airborne, ObstacleKind::Barrier));
+    assert!(clears(sliding, ObstacleKind::Barrier));
+    assert!(!clears(sliding, ObstacleKind::Hurdle));
+}
+
+/// Starts a run, puts one `kind` obstacle just ahead of the player and
+/// optionally taps `key`. Returns whether the run survived it.
+fn survives_close_obstacle(kind: ObstacleKind, key: Option<KeyCode>) -> bool {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    boot(&mut app);
+    tap_key(&mut app, KeyCode::Space);
+    app.world_mut().spawn((
+        SimPosition::new(Vec3::new(0.0, kind.centre_y(), -4.0)),
+        Transform::default(),
+        Obstacle,
+        kind,
+    ));
+    if let Some(key) = key {
+        tap_key(&mut app, key);
+    }
+    // Well past the player, well before the first wave arrives.
+    advance(&mut app, 30);
+    state(&app) == GameState::Playing
+}
+
+#[test]
+fn jump_clears_hurdles_and_slide_clears_barriers() {
+    assert!(!survives_close_obstacle(ObstacleKind::Hurdle, None));
+    assert!(survives_close_obstacle(
+        ObstacleKind::Hurdle,
+        Some(KeyCode::Space)
+    ));
+    assert!(!survives_close_obstacle(
+        ObstacleKind::Hurdle,
+        Some(KeyCode::ArrowDown)
+    ));
+
+    assert!(!survives_close_obstacle(ObstacleKind::Barrier, None));
+    assert!(survives_close_obstacle(
+        ObstacleKind::Barrier,
+        Some(KeyCode::ArrowDown)
+    ));
+    assert!(!survives_close_obstacle(
+        ObstacleKind::Barrier,
+        Some(KeyCode::Space)
+    ));
+}
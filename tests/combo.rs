@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use taurc_ui::collision::hit_half_extents;
+use taurc_ui::combo::{
+    Combo, COMBO_HOLD_SECS, COMBO_MAX, COMBO_STEP, NEAR_MISS_MARGIN, NEAR_MISS_POINTS,
+};
+use taurc_ui::game::{Obstacle, ObstacleKind, PLAYER_SIZE};
+use taurc_ui::headless::{advance, boot, headless_app, state, tap_key, DEFAULT_FRAME_STEP};
+use taurc_ui::interpolation::SimPosition;
+use taurc_ui::powerups::{ActiveEffects, PowerUpKind};
+use taurc_ui::GameState;
+
+/// Starts a run and sends one block past the player `dx` to the side.
+/// Returns the run's combo once the block has gone by.
+fn pass_block(dx: f32, shield: bool) -> (Combo, GameState) {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    boot(&mut app);
+    tap_key(&mut app, KeyCode::Space);
+    if shield {
+        app.world_mut()
+            .resource_mut::<ActiveEffects>()
+            .grant(PowerUpKind::Shield);
+    }
+    app.world_mut().spawn((
+        SimPosition::new(Vec3::new(dx, ObstacleKind::Block.centre_y(), -4.0)),
+        Transform::default(),
+        Obstacle,
+        ObstacleKind::Block,
+    ));
+    advance(&mut app, 30);
+    (app.world().resource::<Combo>().clone(), state(&app))
+}
+
+#[test]
+fn multiplier_builds_caps_and_decays() {
+    let mut combo = Combo::default();
+    assert_eq!(combo.near_miss(), NEAR_MISS_POINTS);
+    assert_eq!(combo.near_miss(), NEAR_MISS_POINTS * (1.0 + COMBO_STEP));
+    for _ in 0..20 {
+        combo.near_miss();
+    }
+    assert_eq!(combo.multiplier, COMBO_MAX);
+    assert_eq!(combo.best_multiplier, COMBO_MAX);
+
+    combo.decay(COMBO_HOLD_SECS);
+    assert_eq!(combo.multiplier, COMBO_MAX);
+    for _ in 0..600 {
+        combo.decay(1.0 / 60.0);
+    }
+    assert_eq!(combo.multiplier, 1.0);
+    assert_eq!(combo.best_multiplier, COMBO_MAX);
+}
+
+#[test]
+fn close_pass_scores_a_near_miss() {
+    let reach = hit_half_extents(PLAYER_SIZE, ObstacleKind::Block.size()).x;
+    let (combo, state) = pass_block(reach + NEAR_MISS_MARGIN * 0.5, false);
+    assert_eq!(state, GameState::Playing);
+    assert_eq!(combo.near_misses, 1);
+    assert_eq!(combo.bonus, NEAR_MISS_POINTS);
+    assert_eq!(combo.multiplier, 1.0 + COMBO_STEP);
+
+    let (wide, _) = pass_block(reach + NEAR_MISS_MARGIN * 2.0, false);
+    assert_eq!(wide.near_misses, 0);
+}
+
+#[test]
+fn a_hit_is_not_a_near_miss() {
+    let (combo, state) = pass_block(0.0, true);
+    assert_eq!(state, GameState::Playing, "the shield takes the hit");
+    assert_eq!(combo.near_misses, 0);
+}
@@ -0,0 +1,196 @@
+use bevy::input::gamepad::{GamepadAxisChangedEvent, GamepadEvent};
+use bevy::input::mouse::MouseMotion;
+use bevy::prelude::*;
+use bevy::window::{CursorGrabMode, PrimaryWindow};
+use taurc_ui::devices::{
+    ActiveDevice, InputDevice, MouseMode, Sensitivity, SensitivityDevice, DEFAULT_DRAG_X_PER_PX,
+    MAX_SENSITIVITY, MIN_SENSITIVITY, SENSITIVITY_STEP,
+};
+use taurc_ui::game::{Player, RunSeed, KEY_STEP_X, TRACK_HALF_X};
+use taurc_ui::headless::{
+    advance, boot, booted_app, connect_gamepad, headless_app, state, tap_key, DEFAULT_FRAME_STEP,
+};
+use taurc_ui::lanes::ControlScheme;
+use taurc_ui::profile::{Profile, PROFILE_KEY};
+use taurc_ui::storage::{MemoryBackend, SaveStore};
+use taurc_ui::GameState;
+
+fn target_x(app: &mut App) -> f32 {
+    let world = app.world_mut();
+    world.query::<&Player>().single(world).target_x
+}
+
+fn windowed_run(mode: MouseMode, scheme: ControlScheme) -> App {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(RunSeed(5));
+    app.world_mut().spawn((Window::default(), PrimaryWindow));
+    boot(&mut app);
+    app.insert_resource(mode).insert_resource(scheme);
+    tap_key(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Playing);
+    app
+}
+
+fn window(app: &mut App) -> Mut<'_, Window> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&mut Window, With<PrimaryWindow>>()
+        .single_mut(world)
+}
+
+/// Puts the cursor `dx` pixels right of the window's centre.
+fn place_cursor(app: &mut App, dx: f32) {
+    let mut window = window(app);
+    let at = Vec2::new(window.width() * 0.5 + dx, window.height() * 0.5);
+    window.set_cursor_position(Some(at));
+    app.update();
+}
+
+fn move_mouse(app: &mut App, dx: f32) {
+    app.world_mut().send_event(MouseMotion {
+        delta: Vec2::new(dx, 0.0),
+    });
+    app.update();
+}
+
+fn active(app: &App) -> Option<InputDevice> {
+    app.world().resource::<ActiveDevice>().0
+}
+
+#[test]
+fn a_resting_cursor_leaves_key_steps_alone() {
+    let mut app = windowed_run(MouseMode::Absolute, ControlScheme::Analog);
+    place_cursor(&mut app, 50.0);
+    assert_eq!(active(&app), Some(InputDevice::Mouse));
+    let under_cursor = 50.0 * DEFAULT_DRAG_X_PER_PX;
+    assert!((target_x(&mut app) - under_cursor).abs() < 1e-5);
+
+    tap_key(&mut app, KeyCode::ArrowLeft);
+    assert_eq!(active(&app), Some(InputDevice::Keyboard));
+    advance(&mut app, 10);
+    assert!((target_x(&mut app) - (under_cursor - KEY_STEP_X)).abs() < 1e-5);
+
+    // Moving the mouse takes over again.
+    place_cursor(&mut app, -100.0);
+    assert_eq!(active(&app), Some(InputDevice::Mouse));
+    assert!((target_x(&mut app) + 100.0 * DEFAULT_DRAG_X_PER_PX).abs() < 1e-5);
+}
+
+#[test]
+fn sensitivity_scales_the_mouse_and_the_stick() {
+    let mut app = windowed_run(MouseMode::Absolute, ControlScheme::Analog);
+    app.insert_resource(Sensitivity {
+        mouse: 2.0 * DEFAULT_DRAG_X_PER_PX,
+        stick: 0.5,
+        ..Default::default()
+    });
+    place_cursor(&mut app, 50.0);
+    assert!((target_x(&mut app) - 100.0 * DEFAULT_DRAG_X_PER_PX).abs() < 1e-5);
+
+    let pad = connect_gamepad(&mut app, 0);
+    app.world_mut()
+        .send_event(GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+            pad,
+            GamepadAxisType::LeftStickX,
+            1.0,
+        )));
+    app.update();
+    assert_eq!(active(&app), Some(InputDevice::Gamepad));
+    advance(&mut app, 5);
+    assert!((target_x(&mut app) - 0.5 * TRACK_HALF_X).abs() < 1e-5);
+}
+
+#[test]
+fn relative_mouse_nudges_and_locks_the_cursor() {
+    let mut app = windowed_run(MouseMode::Relative, ControlScheme::Analog);
+    assert_eq!(window(&mut app).cursor.grab_mode, CursorGrabMode::Locked);
+    assert!(!window(&mut app).cursor.visible);
+
+    tap_key(&mut app, KeyCode::ArrowRight);
+    move_mouse(&mut app, 40.0);
+    let nudged = KEY_STEP_X + 40.0 * DEFAULT_DRAG_X_PER_PX;
+    assert!((target_x(&mut app) - nudged).abs() < 1e-5);
+
+    tap_key(&mut app, KeyCode::KeyP);
+    assert_eq!(window(&mut app).cursor.grab_mode, CursorGrabMode::None);
+    assert!(window(&mut app).cursor.visible);
+}
+
+#[test]
+fn relative_mouse_adds_up_to_a_lane_change() {
+    let mut app = windowed_run(MouseMode::Relative, ControlScheme::ThreeLanes);
+    let lanes = ControlScheme::ThreeLanes.lanes().unwrap();
+    let px = 0.3 * lanes.width() / DEFAULT_DRAG_X_PER_PX;
+    move_mouse(&mut app, px);
+    assert_eq!(target_x(&mut app), lanes.centre(1));
+    move_mouse(&mut app, px);
+    assert_eq!(target_x(&mut app), lanes.centre(2));
+}
+
+#[test]
+fn pointer_settings_are_saved_and_sanitised() {
+    let store = MemoryBackend::default();
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(SaveStore::new(store.clone()));
+    boot(&mut app);
+    tap_key(&mut app, KeyCode::KeyM);
+    let saved: Profile = serde_json::from_str(&store.get(PROFILE_KEY).unwrap()).unwrap();
+    assert_eq!(saved.mouse_mode, MouseMode::Relative);
+
+    store.insert(
+        PROFILE_KEY,
+        serde_json::to_string(&Profile {
+            mouse_mode: MouseMode::Relative,
+            sensitivity: Sensitivity {
+                mouse: 100.0,
+                ..Default::default()
+            },
+            ..saved
+        })
+        .unwrap(),
+    );
+    let mut next_session = headless_app(DEFAULT_FRAME_STEP);
+    next_session.insert_resource(SaveStore::new(store));
+    boot(&mut next_session);
+    assert_eq!(
+        *next_session.world().resource::<MouseMode>(),
+        MouseMode::Relative
+    );
+    let sensitivity = next_session.world().resource::<Sensitivity>();
+    assert_eq!(sensitivity.mouse, DEFAULT_DRAG_X_PER_PX * MAX_SENSITIVITY);
+    assert_eq!(sensitivity.touch, DEFAULT_DRAG_X_PER_PX);
+}
+
+#[test]
+fn the_menu_tunes_each_device_and_saves() {
+    let store = MemoryBackend::default();
+    let mut app = booted_app(5, &store);
+    tap_key(&mut app, KeyCode::Equal);
+    tap_key(&mut app, KeyCode::Equal);
+    tap_key(&mut app, KeyCode::KeyS);
+    assert_eq!(
+        *app.world().resource::<SensitivityDevice>(),
+        SensitivityDevice::Touch
+    );
+    tap_key(&mut app, KeyCode::Minus);
+    tap_key(&mut app, KeyCode::KeyS);
+    for _ in 0..40 {
+        tap_key(&mut app, KeyCode::NumpadAdd);
+    }
+
+    let sensitivity = *app.world().resource::<Sensitivity>();
+    let near = |device, multiple: f32| (sensitivity.multiple(device) - multiple).abs() < 1e-5;
+    assert!(near(SensitivityDevice::Mouse, 1.0 + 2.0 * SENSITIVITY_STEP));
+    assert!(near(SensitivityDevice::Touch, 1.0 - SENSITIVITY_STEP));
+    assert!(near(SensitivityDevice::Stick, MAX_SENSITIVITY));
+    let saved: Profile = serde_json::from_str(&store.get(PROFILE_KEY).unwrap()).unwrap();
+    assert_eq!(saved.sensitivity, sensitivity);
+
+    // Lowering stops at the minimum.
+    let mut lowest = sensitivity;
+    lowest.adjust(SensitivityDevice::Touch, -40);
+    assert!((lowest.multiple(SensitivityDevice::Touch) - MIN_SENSITIVITY).abs() < 1e-5);
+
+    let next_session = booted_app(5, &store);
+    assert_eq!(*next_session.world().resource::<Sensitivity>(), sensitivity);
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use taurc_ui::difficulty::{Difficulty, DifficultyProfile};
+use taurc_ui::headless::{boot, headless_app, state, tap_key, DEFAULT_FRAME_STEP};
+use taurc_ui::profile::{Profile, PROFILE_KEY};
+use taurc_ui::storage::{MemoryBackend, SaveStore};
+use taurc_ui::GameState;
+
+fn preset(difficulty: Difficulty) -> DifficultyProfile {
+    let path = format!(
+        "{}/assets/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        difficulty.asset_path()
+    );
+    ron::de::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn normal_preset_matches_built_in_curve() {
+    assert_eq!(preset(Difficulty::Normal), DifficultyProfile::default());
+}
+
+#[test]
+fn presets_get_harder() {
+    let [easy, normal, hard] = Difficulty::ALL.map(preset);
+    for (lower, higher) in [(&easy, &normal), (&normal, &hard)] {
+        assert!(lower.obstacle_speed < higher.obstacle_speed);
+        assert!(lower.spawn_interval_base > higher.spawn_interval_base);
+        assert!(lower.spawn_interval_min > higher.spawn_interval_min);
+    }
+}
+
+#[test]
+fn menu_choice_drives_the_run_and_is_saved() {
+    let store = MemoryBackend::default();
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(SaveStore::new(store.clone()));
+    boot(&mut app);
+    assert_eq!(*app.world().resource::<Difficulty>(), Difficulty::Normal);
+
+    tap_key(&mut app, KeyCode::KeyD);
+    assert_eq!(*app.world().resource::<Difficulty>(), Difficulty::Hard);
+    let saved: Profile = serde_json::from_str(&store.get(PROFILE_KEY).unwrap()).unwrap();
+    assert_eq!(saved.difficulty, Difficulty::Hard);
+
+    tap_key(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Playing);
+    assert_eq!(
+        *app.world().resource::<DifficultyProfile>(),
+        preset(Difficulty::Hard)
+    );
+
+    // A fresh session starts on the saved preset.
+    let mut next_session = headless_app(DEFAULT_FRAME_STEP);
+    next_session.insert_resource(SaveStore::new(store));
+    boot(&mut next_session);
+    assert_eq!(
+        *next_session.world().resource::<Difficulty>(),
+        Difficulty::Hard
+    );
+}
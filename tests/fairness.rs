@@ -0,0 +1,288 @@
+use bevy::prelude::*;
+use proptest::prelude::*;
+use taurc_ui::collision::hit_half_extents;
+use taurc_ui::difficulty::{Difficulty, DifficultyProfile};
+use taurc_ui::fairness::{Lookahead, Reach};
+use taurc_ui::game::{
+    Obstacle, ObstacleKind, Player, RunSeed, Stance, VerticalAction, OBSTACLE_START_Z, PLAYER_SIZE,
+    PLAYER_Z, SIM_TICK_HZ, TRACK_HALF_X,
+};
+use taurc_ui::headless::{advance, boot, headless_app, state, tap_key, DEFAULT_FRAME_STEP};
+use taurc_ui::interpolation::SimPosition;
+use taurc_ui::lanes::ControlScheme;
+use taurc_ui::GameState;
+
+const RUN_SECS: u32 = 75; // long enough to reach the top wave tier
+const CELL: f32 = 0.005; // brute-force grid spacing
+
+/// One obstacle's path over a tick.
+#[derive(Clone, Copy)]
+struct Sweep {
+    x: f32,
+    z0: f32,
+    z1: f32,
+    kind: ObstacleKind,
+}
+
+impl Sweep {
+    /// Whether it reaches the player's depth at some point in the tick.
+    fn passes(&self) -> bool {
+        let half_z = hit_half_extents(PLAYER_SIZE, self.kind.size()).z;
+        self.z1 > PLAYER_Z - half_z && self.z0 < PLAYER_Z + half_z
+    }
+}
+
+/// Every tick of a run as the obstacles moved through it.
+#[derive(Resource, Default)]
+struct Sweeps {
+    ticks: Vec<Vec<Sweep>>,
+    spawned: usize,
+}
+
+fn record_sweeps(
+    mut sweeps: ResMut<Sweeps>,
+    q: Query<(&SimPosition, &ObstacleKind), With<Obstacle>>,
+    added: Query<(), Added<Obstacle>>,
+) {
+    let tick = q
+        .iter()
+        .map(|(p, kind)| Sweep {
+            x: p.current.x,
+            z0: p.previous.z,
+            z1: p.current.z,
+            kind: *kind,
+        })
+        .collect();
+    sweeps.ticks.push(tick);
+    sweeps.spawned += added.iter().count();
+}
+
+/// Plays `difficulty` from `seed` with nobody to hit, recording every tick.
+fn obstacle_run(
+    seed: u64,
+    difficulty: Difficulty,
+    scheme: ControlScheme,
+) -> (Sweeps, DifficultyProfile) {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(RunSeed(seed))
+        .init_resource::<Sweeps>()
+        .add_systems(
+            FixedPostUpdate,
+            record_sweeps.run_if(in_state(GameState::Playing)),
+        );
+    boot(&mut app);
+    app.insert_resource(difficulty).insert_resource(scheme);
+    tap_key(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Playing);
+
+    let world = app.world_mut();
+    let player = world.query_filtered::<Entity, With<Player>>().single(world);
+    world.despawn(player);
+    advance(&mut app, RUN_SECS * 60);
+
+    let profile = app.world().resource::<DifficultyProfile>().clone();
+    let sweeps = app.world_mut().remove_resource::<Sweeps>().unwrap();
+    (sweeps, profile)
+}
+
+/// Brute force over an X grid, independent of the game's checker: whether a
+/// player starting on the centre line, moving at most `max_step` per tick
+/// and never through a block, lasts every tick.
+fn steerable(ticks: &[Vec<Sweep>], max_step: f32) -> bool {
+    let half = hit_half_extents(PLAYER_SIZE, ObstacleKind::Block.size());
+    let cells = (2.0 * TRACK_HALF_X / CELL).round() as usize + 1;
+    let x = |i: usize| -TRACK_HALF_X + i as f32 * CELL;
+    let reach_cells = (max_step / CELL).floor() as usize;
+    let mut reach = vec![false; cells];
+    reach[cells / 2] = true;
+
+    for tick in ticks {
+        let mut blocked = vec![false; cells];
+        let passing = tick
+            .iter()
+            .filter(|s| s.kind == ObstacleKind::Block && s.passes());
+        for sweep in passing {
+            for (i, cell) in blocked.iter_mut().enumerate() {
+                *cell |= (x(i) - sweep.x).abs() < half.x;
+            }
+        }
+        let mut next = vec![false; cells];
+        // Sweep both ways, remembering the nearest reachable cell with no
+        // obstacle in between.
+        let mut from = None;
+        for i in 0..cells {
+            if blocked[i] {
+                from = None;
+                continue;
+            }
+            if reach[i] {
+                from = Some(i);
+            }
+            next[i] |= from.is_some_and(|f| i - f <= reach_cells);
+        }
+        from = None;
+        for i in (0..cells).rev() {
+            if blocked[i] {
+                from = None;
+                continue;
+            }
+            if reach[i] {
+                from = Some(i);
+            }
+            next[i] |= from.is_some_and(|f| f - i <= reach_cells);
+        }
+        if !next.contains(&true) {
+            return false;
+        }
+        reach = next;
+    }
+    true
+}
+
+/// Whether one jump or slide at a time, earliest first, clears every hurdle
+/// and barrier wherever it is on the track. Uses the game's stance physics
+/// and hit boxes rather than the checker's timing rule.
+fn vertically_clearable(ticks: &[Vec<Sweep>], dt: f32) -> bool {
+    let passing: Vec<Vec<Sweep>> = ticks
+        .iter()
+        .map(|tick| {
+            tick.iter()
+                .filter(|s| s.kind != ObstacleKind::Block && s.passes())
+                .copied()
+                .collect()
+        })
+        .collect();
+    // Stance after each tick of an action started on tick 0, until it ends.
+    let stances = |action| {
+        let mut stance = Stance::Running.start(action);
+        std::iter::from_fn(move || {
+            stance = stance.advance(dt);
+            Some(stance)
+        })
+        .take_while(|s| *s != Stance::Running)
+        .collect::<Vec<_>>()
+    };
+    let jump = stances(VerticalAction::Jump);
+    let slide = stances(VerticalAction::Slide);
+    let clears = |stance: Stance, before: Stance, sweep: &Sweep| {
+        let half_y = hit_half_extents(stance.size(), sweep.kind.size()).y;
+        [stance.centre_y(), before.centre_y()]
+            .iter()
+            .all(|y| (y - sweep.kind.centre_y()).abs() >= half_y)
+    };
+
+    let mut free_from = 0;
+    let mut t = 0;
+    while t < passing.len() {
+        if passing[t].is_empty() {
+            t += 1;
+            continue;
+        }
+        let end = (t..passing.len())
+            .find(|&i| passing[i].is_empty())
+            .unwrap_or(passing.len());
+        let kind = passing[t][0].kind;
+        if passing[t..end].iter().flatten().any(|s| s.kind != kind) {
+            return false;
+        }
+        let action = if kind == ObstacleKind::Hurdle {
+            &jump
+        } else {
+            &slide
+        };
+        let works = |start: usize| {
+            (t..end).all(|i| {
+                let Some(&stance) = action.get(i - start) else {
+                    return false;
+                };
+                let before = match i - start {
+                    0 => Stance::Running,
+                    k => action[k - 1],
+                };
+                passing[i].iter().all(|s| clears(stance, before, s))
+            })
+        };
+        let Some(start) = (free_from.max(t.saturating_sub(action.len()))..=t).find(|&s| works(s))
+        else {
+            return false;
+        };
+        free_from = start + action.len() + 1;
+        t = end;
+    }
+    true
+}
+
+fn difficulty() -> impl Strategy<Value = Difficulty> {
+    prop::sample::select(Difficulty::ALL.to_vec())
+}
+
+fn scheme() -> impl Strategy<Value = ControlScheme> {
+    prop::sample::select(ControlScheme::ALL.to_vec())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(6))]
+
+    #[test]
+    fn every_generated_run_is_survivable(
+        seed in 0..10u64.pow(15),
+        difficulty in difficulty(),
+        scheme in scheme(),
+    ) {
+        let (sweeps, profile) = obstacle_run(seed, difficulty, scheme);
+        prop_assert!(sweeps.spawned as u32 > RUN_SECS, "only {} obstacles", sweeps.spawned);
+        let dt = 1.0 / SIM_TICK_HZ as f32;
+        prop_assert!(steerable(&sweeps.ticks, profile.player_lerp_speed * dt));
+        prop_assert!(vertically_clearable(&sweeps.ticks, dt));
+    }
+}
+
+fn lookahead() -> Lookahead {
+    Lookahead::new(
+        &DifficultyProfile::default(),
+        None,
+        DifficultyProfile::default().obstacle_speed,
+        1.0 / SIM_TICK_HZ as f32,
+    )
+}
+
+fn row(kind: ObstacleKind, gap_at: Option<f32>, z: f32) -> Vec<(Vec2, ObstacleKind)> {
+    (0..=7)
+        .map(|i| -TRACK_HALF_X + i as f32 * 1.2)
+        .filter(|x| gap_at.is_none_or(|g| (x - g).abs() > 1.0))
+        .map(|x| (Vec2::new(x, z), kind))
+        .collect()
+}
+
+fn wall(gap_at: Option<f32>, z: f32) -> Vec<(Vec2, ObstacleKind)> {
+    row(ObstacleKind::Block, gap_at, z)
+}
+
+#[test]
+fn closed_wall_is_unsurvivable() {
+    assert!(!lookahead().survivable(&Reach::at(0.0), &wall(None, OBSTACLE_START_Z)));
+}
+
+#[test]
+fn gap_counts_only_if_reachable_in_time() {
+    let open_wide = wall(Some(TRACK_HALF_X - 0.6), OBSTACLE_START_Z);
+    assert!(lookahead().survivable(&Reach::at(0.0), &open_wide));
+    // The same wall a few metres out leaves no time to get across.
+    let close = wall(Some(TRACK_HALF_X - 0.6), PLAYER_Z - 3.0);
+    assert!(!lookahead().survivable(&Reach::at(-TRACK_HALF_X), &close));
+}
+
+#[test]
+fn back_to_back_jumps_need_time_to_land() {
+    let hurdles = |gap: f32| {
+        let mut both = row(ObstacleKind::Hurdle, None, OBSTACLE_START_Z);
+        both.extend(row(ObstacleKind::Hurdle, None, OBSTACLE_START_Z - gap));
+        both
+    };
+    assert!(lookahead().survivable(&Reach::at(0.0), &hurdles(20.0)));
+    assert!(!lookahead().survivable(&Reach::at(0.0), &hurdles(2.0)));
+
+    let mut mixed = row(ObstacleKind::Hurdle, None, OBSTACLE_START_Z);
+    mixed.extend(row(ObstacleKind::Barrier, None, OBSTACLE_START_Z - 0.5));
+    assert!(!lookahead().survivable(&Reach::at(0.0), &mixed));
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use std::time::Duration;
+use taurc_ui::difficulty::DifficultyProfile;
+use taurc_ui::game::{obstacle_speed, Obstacle, Player, RunSeed, RunStats, Score};
+use taurc_ui::headless::{
+    advance, boot, headless_app, run_until_game_over, started_app, state, tap_key,
+    DEFAULT_FRAME_STEP,
+};
+use taurc_ui::storage::MemoryBackend;
+use taurc_ui::GameState;
+
+const SEED: u64 = 1234;
+
+#[test]
+fn starts_in_menu() {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    boot(&mut app);
+    assert_eq!(state(&app), GameState::Menu);
+    assert!(app
+        .world_mut()
+        .query::<&Player>()
+        .iter(app.world())
+        .next()
+        .is_none());
+}
+
+#[test]
+fn menu_to_playing_spawns_player_and_obstacles() {
+    let mut app = started_app(SEED, &MemoryBackend::default());
+    assert_eq!(state(&app), GameState::Playing);
+    advance(&mut app, 60);
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Player>().iter(world).count(), 1);
+    assert!(world.query::<&Obstacle>().iter(world).count() > 0);
+    assert!(world.resource::<Score>().value > 0.0);
+}
+
+#[test]
+fn idle_player_reaches_game_over_and_restarts() {
+    let mut app = started_app(SEED, &MemoryBackend::default());
+    run_until_game_over(&mut app);
+    assert_eq!(state(&app), GameState::GameOver);
+
+    let score = app.world().resource::<Score>();
+    assert!(score.best > 0.0);
+    assert_eq!(score.best, score.value);
+
+    // Gameplay entities are cleaned up on leaving Playing.
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Player>().iter(world).count(), 0);
+    assert_eq!(world.query::<&Obstacle>().iter(world).count(), 0);
+
+    // R replays the same seed.
+    tap_key(&mut app, KeyCode::KeyR);
+    assert_eq!(state(&app), GameState::Playing);
+    assert_eq!(app.world().resource::<RunSeed>().0, SEED);
+}
+
+#[test]
+fn same_seed_gives_same_run() {
+    let mut a = started_app(SEED, &MemoryBackend::default());
+    let mut b = started_app(SEED, &MemoryBackend::default());
+    let frames_a = run_until_game_over(&mut a);
+    let frames_b = run_until_game_over(&mut b);
+    assert_eq!(frames_a, frames_b);
+    assert_eq!(
+        a.world().resource::<Score>().value,
+        b.world().resource::<Score>().value
+    );
+}
+
+#[test]
+fn run_is_independent_of_frame_rate() {
+    let run_at = |hz: f64| {
+        let mut app = headless_app(Duration::from_secs_f64(1.0 / hz));
+        app.insert_resource(RunSeed(SEED));
+        boot(&mut app);
+        tap_key(&mut app, KeyCode::Space);
+        run_until_game_over(&mut app);
+        app.world().resource::<Score>().value
+    };
+    let reference = run_at(60.0);
+    for hz in [24.0, 30.0, 90.0, 144.0] {
+        assert_eq!(run_at(hz), reference, "outcome changed at {hz} Hz");
+    }
+}
+
+#[test]
+fn bonus_score_does_not_speed_the_run_up() {
+    let stats_after_bonus = |bonus: f32| {
+        let mut app = started_app(SEED, &MemoryBackend::default());
+        advance(&mut app, 60);
+        app.world_mut().resource_mut::<Score>().value += bonus;
+        advance(&mut app, 60);
+        let profile = app.world().resource::<DifficultyProfile>();
+        let stats = *app.world().resource::<RunStats>();
+        (stats, obstacle_speed(0.0, profile))
+    };
+    let (plain, start_speed) = stats_after_bonus(0.0);
+    assert_eq!(stats_after_bonus(5_000.0).0, plain);
+
+    assert!(plain.elapsed > 1.9);
+    assert!(plain.speed > start_speed);
+    assert!(plain.distance > plain.elapsed * start_speed);
+}
+
+#[test]
+fn passed_obstacles_count_as_dodged() {
+    let mut app = started_app(SEED, &MemoryBackend::default());
+    let world = app.world_mut();
+    let player = world.query_filtered::<Entity, With<Player>>().single(world);
+    world.despawn(player);
+    assert_eq!(app.world().resource::<RunStats>().dodged, 0);
+    advance(&mut app, 10 * 60);
+    assert!(app.world().resource::<RunStats>().dodged > 0);
+}
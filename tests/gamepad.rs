@@ -0,0 +1,111 @@
+use bevy::input::gamepad::{GamepadAxisChangedEvent, GamepadEvent};
+use bevy::prelude::*;
+use taurc_ui::game::{Player, RunSeed, KEY_STEP_X, TRACK_HALF_X};
+use taurc_ui::gamepad::ActivePad;
+use taurc_ui::headless::{
+    advance, booted_app, connect_gamepad, disconnect_gamepad, state, tap_button,
+};
+use taurc_ui::lanes::ControlScheme;
+use taurc_ui::pause::PauseState;
+use taurc_ui::storage::MemoryBackend;
+use taurc_ui::GameState;
+
+fn target_x(app: &mut App) -> f32 {
+    let world = app.world_mut();
+    world.query::<&Player>().single(world).target_x
+}
+
+fn stick(app: &mut App, pad: Gamepad, x: f32) {
+    app.world_mut()
+        .send_event(GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+            pad,
+            GamepadAxisType::LeftStickX,
+            x,
+        )));
+    app.update();
+}
+
+fn pad_run(scheme: ControlScheme) -> (App, Gamepad) {
+    let mut app = booted_app(6, &MemoryBackend::default());
+    app.insert_resource(scheme);
+    let pad = connect_gamepad(&mut app, 0);
+    tap_button(&mut app, pad, GamepadButtonType::South);
+    assert_eq!(state(&app), GameState::Playing);
+    (app, pad)
+}
+
+#[test]
+fn south_starts_and_start_restarts() {
+    let (mut app, pad) = pad_run(ControlScheme::Analog);
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::GameOver);
+    advance(&mut app, 2);
+    let seed = app.world().resource::<RunSeed>().0;
+    tap_button(&mut app, pad, GamepadButtonType::Start);
+    assert_eq!(state(&app), GameState::Playing);
+    assert_ne!(app.world().resource::<RunSeed>().0, seed);
+    // The restart press doesn't also pause the new run.
+    advance(&mut app, 2);
+    assert_eq!(
+        *app.world().resource::<State<PauseState>>().get(),
+        PauseState::Running
+    );
+}
+
+#[test]
+fn dpad_steps_and_stick_places() {
+    let (mut app, pad) = pad_run(ControlScheme::Analog);
+    tap_button(&mut app, pad, GamepadButtonType::DPadRight);
+    assert!((target_x(&mut app) - KEY_STEP_X).abs() < 1e-5);
+    tap_button(&mut app, pad, GamepadButtonType::DPadLeft);
+    tap_button(&mut app, pad, GamepadButtonType::DPadLeft);
+    assert!((target_x(&mut app) + KEY_STEP_X).abs() < 1e-5);
+
+    stick(&mut app, pad, 0.5);
+    assert!((target_x(&mut app) - 0.5 * TRACK_HALF_X).abs() < 1e-5);
+    // A resting stick sends nothing, so D-pad steps stick.
+    tap_button(&mut app, pad, GamepadButtonType::DPadLeft);
+    advance(&mut app, 5);
+    assert!((target_x(&mut app) - (0.5 * TRACK_HALF_X - KEY_STEP_X)).abs() < 1e-5);
+}
+
+#[test]
+fn stick_picks_a_lane_in_lane_mode() {
+    let (mut app, pad) = pad_run(ControlScheme::ThreeLanes);
+    let lanes = ControlScheme::ThreeLanes.lanes().unwrap();
+    stick(&mut app, pad, -0.9);
+    assert_eq!(target_x(&mut app), lanes.centre(0));
+    tap_button(&mut app, pad, GamepadButtonType::DPadRight);
+    assert_eq!(target_x(&mut app), lanes.centre(1));
+}
+
+#[test]
+fn losing_the_steering_pad_pauses_and_hands_over() {
+    let (mut app, first) = pad_run(ControlScheme::Analog);
+    let second = connect_gamepad(&mut app, 1);
+    assert_eq!(app.world().resource::<ActivePad>().0, Some(first));
+
+    // The second pad doesn't steer while the first is in.
+    tap_button(&mut app, second, GamepadButtonType::DPadRight);
+    assert_eq!(target_x(&mut app), 0.0);
+
+    disconnect_gamepad(&mut app, first);
+    advance(&mut app, 1);
+    assert_eq!(app.world().resource::<ActivePad>().0, Some(second));
+    assert_eq!(
+        *app.world().resource::<State<PauseState>>().get(),
+        PauseState::Paused
+    );
+
+    tap_button(&mut app, second, GamepadButtonType::Start);
+    assert_eq!(
+        *app.world().resource::<State<PauseState>>().get(),
+        PauseState::Running
+    );
+    tap_button(&mut app, second, GamepadButtonType::DPadRight);
+    assert!((target_x(&mut app) - KEY_STEP_X).abs() < 1e-5);
+
+    disconnect_gamepad(&mut app, second);
+    assert_eq!(app.world().resource::<ActivePad>().0, None);
+}
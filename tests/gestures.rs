@@ -0,0 +1,190 @@
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::prelude::*;
+use taurc_ui::game::RunSeed;
+use taurc_ui::gestures::{Gesture, SwipeDirection, SWIPE_MIN_PX, TAP_SLOP_PX};
+use taurc_ui::headless::{advance, boot, headless_app, state, tap_key, DEFAULT_FRAME_STEP};
+use taurc_ui::GameState;
+
+const LONG_PRESS_FRAMES: u32 = 31; // just past LONG_PRESS_SECS at 60 Hz
+
+#[derive(Resource, Default)]
+struct Seen(Vec<Gesture>);
+
+fn collect(mut gestures: EventReader<Gesture>, mut seen: ResMut<Seen>) {
+    seen.0.extend(gestures.read().copied());
+}
+
+fn app() -> App {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(RunSeed(2))
+        .init_resource::<Seen>()
+        .add_systems(PostUpdate, collect);
+    boot(&mut app);
+    app
+}
+
+/// A paused run: gestures are recognised but nothing acts on them.
+fn paused_app() -> App {
+    let mut app = app();
+    tap_key(&mut app, KeyCode::Space);
+    tap_key(&mut app, KeyCode::KeyP);
+    seen(&mut app);
+    app
+}
+
+fn touch(app: &mut App, phase: TouchPhase, position: Vec2) {
+    app.world_mut().send_event(TouchInput {
+        phase,
+        position,
+        window: Entity::PLACEHOLDER,
+        force: None,
+        id: 0,
+    });
+    app.update();
+}
+
+fn tap(app: &mut App, position: Vec2) {
+    touch(app, TouchPhase::Started, position);
+    touch(app, TouchPhase::Ended, position);
+}
+
+fn seen(app: &mut App) -> Vec<Gesture> {
+    std::mem::take(&mut app.world_mut().resource_mut::<Seen>().0)
+}
+
+#[test]
+fn taps_pair_up_into_double_taps() {
+    let mut app = paused_app();
+    let at = Vec2::new(200.0, 300.0);
+    tap(&mut app, at);
+    assert_eq!(seen(&mut app), [Gesture::Tap(at)]);
+    tap(&mut app, at + Vec2::new(10.0, 0.0));
+    let second = at + Vec2::new(10.0, 0.0);
+    assert_eq!(
+        seen(&mut app),
+        [Gesture::Tap(second), Gesture::DoubleTap(second)]
+    );
+    // A third tap starts a new pair.
+    tap(&mut app, at);
+    assert_eq!(seen(&mut app), [Gesture::Tap(at)]);
+
+    // Too late, or too far away, for a double tap.
+    advance(&mut app, 30);
+    tap(&mut app, at);
+    tap(&mut app, at + Vec2::new(200.0, 0.0));
+    assert_eq!(
+        seen(&mut app),
+        [Gesture::Tap(at), Gesture::Tap(at + Vec2::new(200.0, 0.0))]
+    );
+}
+
+#[test]
+fn a_held_touch_long_presses_instead_of_tapping() {
+    let mut app = paused_app();
+    let at = Vec2::new(200.0, 300.0);
+    touch(&mut app, TouchPhase::Started, at);
+    advance(&mut app, LONG_PRESS_FRAMES);
+    assert_eq!(seen(&mut app), [Gesture::LongPress(at)]);
+    advance(&mut app, LONG_PRESS_FRAMES);
+    touch(&mut app, TouchPhase::Ended, at);
+    assert!(seen(&mut app).is_empty());
+}
+
+#[test]
+fn drags_swipe_once_and_never_tap() {
+    let mut app = paused_app();
+    let at = Vec2::new(200.0, 300.0);
+    touch(&mut app, TouchPhase::Started, at);
+    touch(&mut app, TouchPhase::Moved, at + Vec2::new(5.0, -30.0));
+    assert_eq!(seen(&mut app), [Gesture::Drag(Vec2::new(5.0, -30.0))]);
+    touch(
+        &mut app,
+        TouchPhase::Moved,
+        at + Vec2::new(5.0, -SWIPE_MIN_PX),
+    );
+    assert_eq!(
+        seen(&mut app),
+        [
+            Gesture::Drag(Vec2::new(0.0, 30.0 - SWIPE_MIN_PX)),
+            Gesture::Swipe(SwipeDirection::Up)
+        ]
+    );
+    touch(
+        &mut app,
+        TouchPhase::Moved,
+        at + Vec2::new(-100.0, -SWIPE_MIN_PX),
+    );
+    assert_eq!(seen(&mut app), [Gesture::Drag(Vec2::new(-105.0, 0.0))]);
+    // Wandered too far to long-press, even back at the start.
+    touch(&mut app, TouchPhase::Moved, at);
+    advance(&mut app, LONG_PRESS_FRAMES);
+    touch(&mut app, TouchPhase::Ended, at);
+    assert_eq!(
+        seen(&mut app),
+        [Gesture::Drag(Vec2::new(100.0, SWIPE_MIN_PX))]
+    );
+
+    // A little wobble is still a tap.
+    touch(&mut app, TouchPhase::Started, at);
+    touch(
+        &mut app,
+        TouchPhase::Moved,
+        at + Vec2::splat(TAP_SLOP_PX * 0.5),
+    );
+    touch(&mut app, TouchPhase::Ended, at);
+    assert_eq!(seen(&mut app).last(), Some(&Gesture::Tap(at)));
+}
+
+#[test]
+fn a_touch_left_over_from_the_run_does_not_restart() {
+    let mut app = app();
+    tap_key(&mut app, KeyCode::Space);
+    let at = Vec2::new(200.0, 300.0);
+    touch(&mut app, TouchPhase::Started, at);
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::GameOver);
+    advance(&mut app, 2);
+    touch(&mut app, TouchPhase::Moved, at + Vec2::new(0.0, 2.0));
+    touch(&mut app, TouchPhase::Ended, at);
+    advance(&mut app, 2);
+    assert_eq!(state(&app), GameState::GameOver);
+
+    // A fresh tap does.
+    let seed = app.world().resource::<RunSeed>().0;
+    tap(&mut app, at);
+    advance(&mut app, 1);
+    assert_eq!(state(&app), GameState::Playing);
+    assert_ne!(app.world().resource::<RunSeed>().0, seed);
+}
+
+#[test]
+fn a_long_press_on_game_over_retries_the_seed() {
+    let mut app = app();
+    tap_key(&mut app, KeyCode::Space);
+    let seed = app.world().resource::<RunSeed>().0;
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::GameOver);
+    advance(&mut app, 2);
+    touch(&mut app, TouchPhase::Started, Vec2::new(200.0, 300.0));
+    advance(&mut app, LONG_PRESS_FRAMES);
+    assert_eq!(state(&app), GameState::Playing);
+    assert_eq!(app.world().resource::<RunSeed>().0, seed);
+    // Lifting the finger in the new run does nothing.
+    touch(&mut app, TouchPhase::Ended, Vec2::new(200.0, 300.0));
+    advance(&mut app, 2);
+    assert_eq!(state(&app), GameState::Playing);
+}
+
+#[test]
+fn a_tap_on_the_menu_starts_when_it_lifts() {
+    let mut app = app();
+    let at = Vec2::new(200.0, 300.0);
+    touch(&mut app, TouchPhase::Started, at);
+    advance(&mut app, 2);
+    assert_eq!(state(&app), GameState::Menu);
+    touch(&mut app, TouchPhase::Ended, at);
+    advance(&mut app, 1);
+    assert_eq!(state(&app), GameState::Playing);
+}
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use taurc_ui::game::Player;
+use taurc_ui::ghost::{BestGhost, Ghost, GhostRecorder};
+use taurc_ui::headless::{run_until_game_over, started_app, state, tap_key};
+use taurc_ui::interpolation::SimPosition;
+use taurc_ui::storage::MemoryBackend;
+use taurc_ui::GameState;
+
+const SEED: u64 = 1234;
+
+fn ghost_x(app: &mut App) -> Option<f32> {
+    app.world_mut()
+        .query_filtered::<&SimPosition, With<Ghost>>()
+        .iter(app.world())
+        .next()
+        .map(|p| p.current.x)
+}
+
+fn best(app: &App) -> Option<taurc_ui::ghost::GhostTrack> {
+    app.world().resource::<BestGhost>().0.clone()
+}
+
+#[test]
+fn first_run_becomes_the_ghost() {
+    let mut app = started_app(SEED, &MemoryBackend::default());
+    // No best yet: no ghost and no gap to show.
+    assert!(ghost_x(&mut app).is_none());
+    let gap = {
+        let world = app.world();
+        world
+            .resource::<GhostRecorder>()
+            .gap(world.resource::<BestGhost>())
+    };
+    assert!(gap.is_none());
+
+    run_until_game_over(&mut app);
+    let track = best(&app).expect("best run stored");
+    assert!(!track.xs.is_empty());
+    assert!(track.distance > 0.0);
+}
+
+#[test]
+fn ghost_follows_best_trajectory_and_reports_gap() {
+    let mut app = started_app(SEED, &MemoryBackend::default());
+    run_until_game_over(&mut app);
+    let track = best(&app).unwrap();
+
+    // Same seed again, but steer away so the live run differs.
+    tap_key(&mut app, KeyCode::KeyR);
+    assert_eq!(state(&app), GameState::Playing);
+    tap_key(&mut app, KeyCode::ArrowRight);
+    for _ in 0..20 {
+        app.update();
+        let world = app.world();
+        let recorder = world.resource::<GhostRecorder>();
+        let tick = recorder.0.xs.len() - 1;
+        let gap = recorder.gap(world.resource::<BestGhost>()).unwrap();
+        assert!(gap < 0.0, "best run is still ahead");
+        assert_eq!(ghost_x(&mut app), Some(track.xs[tick]));
+    }
+    let player_x = app
+        .world_mut()
+        .query_filtered::<&SimPosition, With<Player>>()
+        .single(app.world())
+        .current
+        .x;
+    assert_ne!(Some(player_x), ghost_x(&mut app));
+}
+
+#[test]
+fn shorter_run_keeps_previous_ghost() {
+    let mut app = started_app(SEED, &MemoryBackend::default());
+    run_until_game_over(&mut app);
+    let first = best(&app).unwrap();
+
+    // An identical replay of the seed can't beat it.
+    tap_key(&mut app, KeyCode::KeyR);
+    run_until_game_over(&mut app);
+    assert_eq!(best(&app), Some(first));
+}
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use taurc_ui::game::{Player, KEY_STEP_X};
+use taurc_ui::headless::{
+    advance, boot, booted_app, headless_app, state, tap_key, DEFAULT_FRAME_STEP,
+};
+use taurc_ui::input_map::{key_name, Action, InputMap, Rebinding, MAX_KEYS_PER_ACTION};
+use taurc_ui::profile::{Profile, PROFILE_KEY};
+use taurc_ui::storage::{MemoryBackend, SaveStore};
+use taurc_ui::GameState;
+
+fn target_x(app: &mut App) -> f32 {
+    let world = app.world_mut();
+    world.query::<&Player>().single(world).target_x
+}
+
+#[test]
+fn binding_takes_a_key_only_within_its_group() {
+    let mut map = InputMap::default();
+    map.bind(Action::MoveLeft, KeyCode::KeyD);
+    assert!(map.keys(Action::MoveLeft).contains(&KeyCode::KeyD));
+    assert_eq!(map.keys(Action::MoveRight), &[KeyCode::ArrowRight]);
+
+    // Space keeps starting runs after moving Jump off it.
+    map.bind(Action::Slide, KeyCode::Space);
+    assert!(!map.keys(Action::Jump).contains(&KeyCode::Space));
+    assert!(map.keys(Action::Confirm).contains(&KeyCode::Space));
+
+    for key in [KeyCode::KeyJ, KeyCode::KeyK, KeyCode::KeyL] {
+        map.bind(Action::Retry, key);
+    }
+    assert_eq!(map.keys(Action::Retry).len(), MAX_KEYS_PER_ACTION);
+    assert_eq!(map.keys(Action::Retry)[0], KeyCode::KeyJ);
+
+    map.clear(Action::WatchReplay);
+    assert_eq!(map.describe(Action::WatchReplay), "-");
+    assert_eq!(map.describe(Action::MoveLeft), "Left / A / D");
+    assert_eq!(key_name(KeyCode::Digit4), "4");
+}
+
+#[test]
+fn rebound_keys_steer_and_old_ones_do_not() {
+    let mut app = booted_app(4, &MemoryBackend::default());
+    let mut map = InputMap::default();
+    map.clear(Action::MoveRight);
+    map.bind(Action::MoveRight, KeyCode::KeyL);
+    app.insert_resource(map);
+    tap_key(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Playing);
+
+    tap_key(&mut app, KeyCode::ArrowRight);
+    assert_eq!(target_x(&mut app), 0.0);
+    tap_key(&mut app, KeyCode::KeyL);
+    assert!((target_x(&mut app) - KEY_STEP_X).abs() < 1e-5);
+}
+
+#[test]
+fn the_bindings_screen_rebinds_and_saves() {
+    let store = MemoryBackend::default();
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(SaveStore::new(store.clone()));
+    boot(&mut app);
+    tap_key(&mut app, KeyCode::KeyK);
+    assert_eq!(state(&app), GameState::Bindings);
+
+    // Jump is third in the list.
+    tap_key(&mut app, KeyCode::ArrowDown);
+    tap_key(&mut app, KeyCode::ArrowDown);
+    assert_eq!(app.world().resource::<Rebinding>().action(), Action::Jump);
+    tap_key(&mut app, KeyCode::Enter);
+    assert!(app.world().resource::<Rebinding>().listening);
+    tap_key(&mut app, KeyCode::KeyJ);
+    assert!(!app.world().resource::<Rebinding>().listening);
+    assert_eq!(state(&app), GameState::Bindings);
+    tap_key(&mut app, KeyCode::Escape);
+    advance(&mut app, 1);
+    assert_eq!(state(&app), GameState::Menu);
+
+    let saved: Profile = serde_json::from_str(&store.get(PROFILE_KEY).unwrap()).unwrap();
+    assert!(saved.bindings.keys(Action::Jump).contains(&KeyCode::KeyJ));
+
+    let mut next_session = headless_app(DEFAULT_FRAME_STEP);
+    next_session.insert_resource(SaveStore::new(store));
+    boot(&mut next_session);
+    assert_eq!(*next_session.world().resource::<InputMap>(), saved.bindings);
+}
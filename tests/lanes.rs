@@ -0,0 +1,116 @@
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::prelude::*;
+use taurc_ui::game::{Player, TRACK_HALF_X};
+use taurc_ui::headless::{
+    advance, boot, booted_app, headless_app, state, tap_key, DEFAULT_FRAME_STEP,
+};
+use taurc_ui::interpolation::SimPosition;
+use taurc_ui::lanes::{ControlScheme, Lanes};
+use taurc_ui::profile::{Profile, PROFILE_KEY};
+use taurc_ui::storage::{MemoryBackend, SaveStore};
+use taurc_ui::GameState;
+
+fn player_x(app: &mut App) -> (f32, f32) {
+    let world = app.world_mut();
+    let (p, pos) = world.query::<(&Player, &SimPosition)>().single(world);
+    (p.target_x, pos.current.x)
+}
+
+fn lane_run(scheme: ControlScheme) -> App {
+    let mut app = booted_app(5, &MemoryBackend::default());
+    app.insert_resource(scheme);
+    tap_key(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Playing);
+    app
+}
+
+fn touch(app: &mut App, phase: TouchPhase, position: Vec2) {
+    app.world_mut().send_event(TouchInput {
+        phase,
+        position,
+        window: Entity::PLACEHOLDER,
+        force: None,
+        id: 0,
+    });
+    app.update();
+}
+
+#[test]
+fn lanes_split_the_track_evenly() {
+    for lanes in [Lanes(3), Lanes(5)] {
+        let n = lanes.count();
+        assert!((lanes.centre(0) - lanes.width() / 2.0 + TRACK_HALF_X).abs() < 1e-5);
+        assert!((lanes.centre(n - 1) + lanes.width() / 2.0 - TRACK_HALF_X).abs() < 1e-5);
+        assert_eq!(lanes.centre(n / 2), 0.0);
+        for lane in 0..n {
+            assert_eq!(lanes.nearest(lanes.centre(lane)), lane);
+        }
+        assert_eq!(lanes.nearest(-100.0), 0);
+        assert_eq!(lanes.nearest(100.0), n - 1);
+        assert_eq!(lanes.step(lanes.centre(0), -1), lanes.centre(0));
+        assert_eq!(lanes.step(lanes.centre(n - 1), 1), lanes.centre(n - 1));
+    }
+}
+
+#[test]
+fn keys_snap_between_lanes() {
+    let lanes = Lanes(3);
+    let mut app = lane_run(ControlScheme::ThreeLanes);
+    tap_key(&mut app, KeyCode::ArrowLeft);
+    assert_eq!(player_x(&mut app).0, lanes.centre(0));
+    // Already in the leftmost lane.
+    tap_key(&mut app, KeyCode::ArrowLeft);
+    assert_eq!(player_x(&mut app).0, lanes.centre(0));
+    advance(&mut app, 60);
+    assert_eq!(player_x(&mut app).1, lanes.centre(0));
+
+    tap_key(&mut app, KeyCode::KeyD);
+    tap_key(&mut app, KeyCode::KeyD);
+    assert_eq!(player_x(&mut app).0, lanes.centre(2));
+}
+
+#[test]
+fn horizontal_swipes_step_one_lane_each() {
+    let lanes = Lanes(5);
+    let mut app = lane_run(ControlScheme::FiveLanes);
+    let start = Vec2::new(300.0, 400.0);
+    touch(&mut app, TouchPhase::Started, start);
+    // A long drag is still one lane, not a continuous move.
+    touch(&mut app, TouchPhase::Moved, start + Vec2::new(60.0, 5.0));
+    touch(&mut app, TouchPhase::Moved, start + Vec2::new(200.0, 5.0));
+    touch(&mut app, TouchPhase::Ended, start + Vec2::new(200.0, 5.0));
+    assert_eq!(player_x(&mut app).0, lanes.centre(3));
+
+    touch(&mut app, TouchPhase::Started, start);
+    touch(&mut app, TouchPhase::Moved, start - Vec2::new(80.0, 0.0));
+    touch(&mut app, TouchPhase::Ended, start - Vec2::new(80.0, 0.0));
+    assert_eq!(player_x(&mut app).0, lanes.centre(2));
+}
+
+#[test]
+fn menu_choice_is_saved() {
+    let store = MemoryBackend::default();
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(SaveStore::new(store.clone()));
+    boot(&mut app);
+    assert_eq!(
+        *app.world().resource::<ControlScheme>(),
+        ControlScheme::Analog
+    );
+
+    tap_key(&mut app, KeyCode::KeyC);
+    assert_eq!(
+        *app.world().resource::<ControlScheme>(),
+        ControlScheme::ThreeLanes
+    );
+    let saved: Profile = serde_json::from_str(&store.get(PROFILE_KEY).unwrap()).unwrap();
+    assert_eq!(saved.controls, ControlScheme::ThreeLanes);
+
+    let mut next_session = headless_app(DEFAULT_FRAME_STEP);
+    next_session.insert_resource(SaveStore::new(store));
+    boot(&mut next_session);
+    assert_eq!(
+        *next_session.world().resource::<ControlScheme>(),
+        ControlScheme::ThreeLanes
+    );
+}
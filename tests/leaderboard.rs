@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use taurc_ui::game::Score;
+use taurc_ui::headless::{advance, booted_app, run_until_game_over, state, tap_key};
+use taurc_ui::leaderboard::{
+    format_date, rank, Leaderboard, LeaderboardBackend, LeaderboardClient, LeaderboardEntry,
+    MemoryLeaderboard, ScoreSubmission, LEADERBOARD_LEN,
+};
+use taurc_ui::profile::{PlayerName, Profile, PROFILE_KEY};
+use taurc_ui::storage::MemoryBackend;
+use taurc_ui::GameState;
+
+const SEED: u64 = 42;
+
+fn app_with_board(board: &MemoryLeaderboard, store: &MemoryBackend) -> App {
+    let mut app = booted_app(SEED, store);
+    app.insert_resource(LeaderboardClient::new(board.clone()));
+    app
+}
+
+fn play_one_run(app: &mut App) {
+    tap_key(app, KeyCode::Space);
+    run_until_game_over(app);
+}
+
+fn entry(score: f32, date: u64) -> LeaderboardEntry {
+    LeaderboardEntry {
+        name: "A".into(),
+        score,
+        seed: 0,
+        duration_secs: score / 10.0,
+        date,
+    }
+}
+
+#[test]
+fn finished_run_is_submitted() {
+    let board = MemoryLeaderboard::default();
+    let mut app = app_with_board(&board, &MemoryBackend::default());
+    play_one_run(&mut app);
+
+    let score = app.world().resource::<Score>().value;
+    let entries = board.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, "PLAYER");
+    assert_eq!(entries[0].seed, SEED);
+    assert_eq!(entries[0].score, score);
+    assert!(entries[0].date > 0);
+}
+
+#[test]
+fn quit_run_is_not_submitted() {
+    let board = MemoryLeaderboard::default();
+    let mut app = app_with_board(&board, &MemoryBackend::default());
+    tap_key(&mut app, KeyCode::Space);
+    advance(&mut app, 30);
+    tap_key(&mut app, KeyCode::KeyP);
+    tap_key(&mut app, KeyCode::KeyQ);
+    assert_eq!(state(&app), GameState::Menu);
+    assert!(board.entries().is_empty());
+}
+
+#[test]
+fn menu_opens_board_and_back() {
+    let board = MemoryLeaderboard::default();
+    board.submit(ScoreSubmission {
+        name: "ZED".into(),
+        score: 120.0,
+        seed: 3,
+        duration_secs: 12.0,
+    });
+    let mut app = app_with_board(&board, &MemoryBackend::default());
+    tap_key(&mut app, KeyCode::KeyL);
+    assert_eq!(state(&app), GameState::Leaderboard);
+    let shown = app.world().resource::<Leaderboard>();
+    assert!(!shown.loading);
+    assert_eq!(shown.entries, board.entries());
+    assert_eq!(shown.entries[0].name, "ZED");
+
+    tap_key(&mut app, KeyCode::Escape);
+    assert_eq!(state(&app), GameState::Menu);
+}
+
+#[test]
+fn name_is_edited_on_board_and_saved() {
+    let board = MemoryLeaderboard::default();
+    let store = MemoryBackend::default();
+    let mut app = app_with_board(&board, &store);
+    tap_key(&mut app, KeyCode::KeyL);
+    for _ in 0.."PLAYER".len() {
+        tap_key(&mut app, KeyCode::Backspace);
+    }
+    for key in [KeyCode::KeyA, KeyCode::KeyB, KeyCode::Digit7] {
+        tap_key(&mut app, key);
+    }
+    tap_key(&mut app, KeyCode::Enter);
+    assert_eq!(state(&app), GameState::Menu);
+    assert_eq!(app.world().resource::<PlayerName>().0, "AB7");
+
+    let saved: Profile = serde_json::from_str(&store.get(PROFILE_KEY).unwrap()).unwrap();
+    assert_eq!(saved.name, "AB7");
+
+    play_one_run(&mut app);
+    assert_eq!(board.entries()[0].name, "AB7");
+}
+
+#[test]
+fn rank_keeps_best_runs_first() {
+    let mut entries: Vec<_> = (0..LEADERBOARD_LEN as u64 + 3)
+        .map(|i| entry(i as f32, 100 - i))
+        .collect();
+    entries.push(entry(5.0, 1));
+    rank(&mut entries);
+    assert_eq!(entries.len(), LEADERBOARD_LEN);
+    assert!(entries.windows(2).all(|w| w[0].score >= w[1].score));
+    assert_eq!(entries[0].score, LEADERBOARD_LEN as f32 + 2.0);
+    // Of the two 5s, the earlier run ranks higher.
+    let fives: Vec<_> = entries.iter().filter(|e| e.score == 5.0).collect();
+    assert_eq!(fives[0].date, 1);
+}
+
+#[test]
+fn dates_format_as_utc_days() {
+    assert_eq!(format_date(0), "1970-01-01");
+    assert_eq!(format_date(951_782_400), "2000-02-29");
+    assert_eq!(format_date(1_790_000_000), "2026-09-21");
+}
@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use taurc_ui::game::{Player, TRACK_HALF_X};
+use taurc_ui::headless::{
+    advance, boot, booted_app, headless_app, state, tap_key, DEFAULT_FRAME_STEP,
+};
+use taurc_ui::lanes::ControlScheme;
+use taurc_ui::motion::{
+    MotionInput, ScriptedMotion, Tilt, TiltSettings, TILT_DEAD_ZONE, TILT_FULL, TILT_MAX_DEAD_ZONE,
+    TILT_STEP,
+};
+use taurc_ui::profile::{Profile, PROFILE_KEY};
+use taurc_ui::storage::{MemoryBackend, SaveStore};
+use taurc_ui::GameState;
+
+const LEVEL: f32 = 0.2; // how the phone is held when tilt is turned on
+
+fn target_x(app: &mut App) -> f32 {
+    let world = app.world_mut();
+    world.query::<&Player>().single(world).target_x
+}
+
+/// A run with tilt turned on from the menu while the phone is held at
+/// [`LEVEL`].
+fn tilt_run(store: &MemoryBackend, scheme: ControlScheme) -> (App, ScriptedMotion) {
+    let phone = ScriptedMotion::default();
+    let mut app = booted_app(7, store);
+    app.insert_resource(MotionInput::new(phone.clone()))
+        .insert_resource(scheme);
+    phone.set(LEVEL);
+    tap_key(&mut app, KeyCode::KeyT);
+    assert!(app.world().resource::<TiltSettings>().enabled);
+    tap_key(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Playing);
+    (app, phone)
+}
+
+#[test]
+fn tilt_maps_past_the_dead_zone_to_the_edge() {
+    let settings = TiltSettings {
+        enabled: true,
+        neutral: LEVEL,
+        ..Default::default()
+    };
+    let x = |roll: f32| settings.target_x(Tilt { roll: LEVEL + roll });
+    assert_eq!(x(0.0), 0.0);
+    assert_eq!(x(TILT_DEAD_ZONE), 0.0);
+    assert_eq!(x(-TILT_DEAD_ZONE * 0.5), 0.0);
+    let halfway = (TILT_DEAD_ZONE + TILT_FULL) * 0.5;
+    assert!((x(halfway) - TRACK_HALF_X * 0.5).abs() < 1e-4);
+    assert!((x(-halfway) + TRACK_HALF_X * 0.5).abs() < 1e-4);
+    assert!((x(TILT_FULL) - TRACK_HALF_X).abs() < 1e-4);
+    assert_eq!(x(1.5), TRACK_HALF_X);
+    assert_eq!(x(-1.5), -TRACK_HALF_X);
+
+    let broken = TiltSettings {
+        dead_zone: 2.0,
+        full_tilt: -1.0,
+        ..settings
+    }
+    .clamped();
+    assert_eq!(broken.dead_zone, TILT_DEAD_ZONE);
+    assert_eq!(broken.full_tilt, TILT_FULL);
+    assert_eq!(broken.neutral, LEVEL);
+}
+
+#[test]
+fn tilting_steers_from_the_calibrated_level() {
+    let (mut app, phone) = tilt_run(&MemoryBackend::default(), ControlScheme::Analog);
+    assert_eq!(app.world().resource::<TiltSettings>().neutral, LEVEL);
+    assert_eq!(target_x(&mut app), 0.0);
+
+    phone.set(LEVEL + TILT_FULL);
+    app.update();
+    assert!((target_x(&mut app) - TRACK_HALF_X).abs() < 1e-4);
+    // Tilt owns sideways steering while it's on.
+    tap_key(&mut app, KeyCode::ArrowLeft);
+    assert!((target_x(&mut app) - TRACK_HALF_X).abs() < 1e-4);
+
+    // Without a reading the keys steer again.
+    phone.clear();
+    app.update();
+    tap_key(&mut app, KeyCode::ArrowLeft);
+    assert!(target_x(&mut app) < TRACK_HALF_X - 1e-3);
+}
+
+#[test]
+fn scripted_tilt_snaps_to_lanes() {
+    let (mut app, phone) = tilt_run(&MemoryBackend::default(), ControlScheme::ThreeLanes);
+    let lanes = ControlScheme::ThreeLanes.lanes().unwrap();
+    phone.queue([LEVEL - 0.1, LEVEL - TILT_FULL, LEVEL + 0.3]);
+    app.update();
+    assert_eq!(target_x(&mut app), lanes.centre(1));
+    app.update();
+    assert_eq!(target_x(&mut app), lanes.centre(0));
+    app.update();
+    assert_eq!(target_x(&mut app), lanes.centre(2));
+    // The last reading holds.
+    advance(&mut app, 5);
+    assert_eq!(target_x(&mut app), lanes.centre(2));
+}
+
+#[test]
+fn tilt_settings_are_saved() {
+    let store = MemoryBackend::default();
+    let (_app, _phone) = tilt_run(&store, ControlScheme::Analog);
+    let saved: Profile = serde_json::from_str(&store.get(PROFILE_KEY).unwrap()).unwrap();
+    assert!(saved.tilt.enabled);
+    assert_eq!(saved.tilt.neutral, LEVEL);
+
+    let mut next_session = headless_app(DEFAULT_FRAME_STEP);
+    next_session.insert_resource(SaveStore::new(store));
+    boot(&mut next_session);
+    assert_eq!(*next_session.world().resource::<TiltSettings>(), saved.tilt);
+}
+
+#[test]
+fn the_menu_tunes_the_tilt_angles_and_saves() {
+    let store = MemoryBackend::default();
+    let mut app = booted_app(7, &store);
+    let saved = || -> TiltSettings {
+        serde_json::from_str::<Profile>(&store.get(PROFILE_KEY).unwrap())
+            .unwrap()
+            .tilt
+    };
+
+    // The dead zone is tuned first.
+    tap_key(&mut app, KeyCode::BracketRight);
+    tap_key(&mut app, KeyCode::BracketRight);
+    assert!((saved().dead_zone - (TILT_DEAD_ZONE + 2.0 * TILT_STEP)).abs() < 1e-4);
+    for _ in 0..10 {
+        tap_key(&mut app, KeyCode::BracketRight);
+    }
+    assert!((saved().dead_zone - TILT_MAX_DEAD_ZONE).abs() < 1e-4);
+
+    // Z moves on to full tilt, which can't come down into the dead zone.
+    tap_key(&mut app, KeyCode::KeyZ);
+    tap_key(&mut app, KeyCode::BracketLeft);
+    assert!((saved().full_tilt - (TILT_FULL - TILT_STEP)).abs() < 1e-4);
+    for _ in 0..10 {
+        tap_key(&mut app, KeyCode::BracketLeft);
+    }
+    let tilt = saved();
+    assert!((tilt.full_tilt - (TILT_MAX_DEAD_ZONE + TILT_STEP)).abs() < 1e-4);
+    assert!((tilt.dead_zone - TILT_MAX_DEAD_ZONE).abs() < 1e-4);
+    assert_eq!(*app.world().resource::<TiltSettings>(), tilt);
+}
@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+use bevy::window::WindowFocused;
+use taurc_ui::game::{Obstacle, Score};
+use taurc_ui::headless::{advance, run_until_game_over, started_app, state, tap_key};
+use taurc_ui::interpolation::SimPosition;
+use taurc_ui::pause::PauseState;
+use taurc_ui::profile::RunHistory;
+use taurc_ui::storage::MemoryBackend;
+use taurc_ui::GameState;
+
+const SEED: u64 = 99;
+
+/// Half a second into a run, with obstacles on the way.
+fn running_app() -> App {
+    let mut app = started_app(SEED, &MemoryBackend::default());
+    advance(&mut app, 30);
+    app
+}
+
+fn pause_state(app: &App) -> Option<PauseState> {
+    app.world()
+        .get_resource::<State<PauseState>>()
+        .map(|s| s.get().clone())
+}
+
+fn obstacle_zs(app: &mut App) -> Vec<f32> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&SimPosition, With<Obstacle>>()
+        .iter(world)
+        .map(|p| p.current.z)
+        .collect()
+}
+
+#[test]
+fn p_freezes_the_run_until_resumed() {
+    let mut app = running_app();
+    tap_key(&mut app, KeyCode::KeyP);
+    assert_eq!(state(&app), GameState::Playing);
+    assert_eq!(pause_state(&app), Some(PauseState::Paused));
+
+    let score = app.world().resource::<Score>().value;
+    let zs = obstacle_zs(&mut app);
+    advance(&mut app, 120);
+    assert_eq!(app.world().resource::<Score>().value, score);
+    assert_eq!(obstacle_zs(&mut app), zs);
+
+    tap_key(&mut app, KeyCode::Escape);
+    assert_eq!(pause_state(&app), Some(PauseState::Running));
+    advance(&mut app, 5);
+    assert!(app.world().resource::<Score>().value > score);
+}
+
+#[test]
+fn losing_focus_pauses() {
+    let mut app = running_app();
+    app.world_mut().send_event(WindowFocused {
+        window: Entity::PLACEHOLDER,
+        focused: false,
+    });
+    app.update();
+    assert_eq!(pause_state(&app), Some(PauseState::Paused));
+
+    // Regaining focus leaves resuming to the player.
+    app.world_mut().send_event(WindowFocused {
+        window: Entity::PLACEHOLDER,
+        focused: true,
+    });
+    advance(&mut app, 3);
+    assert_eq!(pause_state(&app), Some(PauseState::Paused));
+}
+
+#[test]
+fn quit_from_pause_ends_the_run() {
+    let mut app = running_app();
+    tap_key(&mut app, KeyCode::KeyP);
+    let score = app.world().resource::<Score>().value;
+    tap_key(&mut app, KeyCode::KeyQ);
+    assert_eq!(state(&app), GameState::Menu);
+    assert_eq!(pause_state(&app), None);
+    assert!(!app.world().resource::<Time<Virtual>>().is_paused());
+
+    let history = &app.world().resource::<RunHistory>().0;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].score, score);
+    assert_eq!(app.world().resource::<Score>().best, score);
+}
+
+#[test]
+fn pausing_does_not_change_the_run() {
+    let final_score = |pause_at: Option<u32>| {
+        let mut app = running_app();
+        if let Some(frames) = pause_at {
+            advance(&mut app, frames);
+            tap_key(&mut app, KeyCode::KeyP);
+            advance(&mut app, 90);
+            tap_key(&mut app, KeyCode::KeyP);
+        }
+        run_until_game_over(&mut app);
+        app.world().resource::<Score>().value
+    };
+    assert_eq!(final_score(None), final_score(Some(20)));
+}
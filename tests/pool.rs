@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use taurc_ui::difficulty::Difficulty;
+use taurc_ui::game::{Obstacle, ObstacleKind, Player, RunSeed};
+use taurc_ui::headless::{
+    advance, boot, booted_app, headless_app, state, tap_key, DEFAULT_FRAME_STEP,
+};
+use taurc_ui::pool::{ObstaclePool, POOL_PREALLOC};
+use taurc_ui::storage::MemoryBackend;
+use taurc_ui::GameState;
+
+/// Obstacle entities created, and the most in play at once.
+#[derive(Resource, Default)]
+struct Counts {
+    created: usize,
+    peak_active: usize,
+}
+
+fn count(
+    mut counts: ResMut<Counts>,
+    created: Query<(), Added<ObstacleKind>>,
+    active: Query<(), With<Obstacle>>,
+) {
+    counts.created += created.iter().count();
+    counts.peak_active = counts.peak_active.max(active.iter().count());
+}
+
+#[test]
+fn hard_runs_reuse_the_preallocated_obstacles() {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(RunSeed(21))
+        .insert_resource(Difficulty::Hard)
+        .init_resource::<Counts>()
+        .add_systems(Last, count);
+    boot(&mut app);
+    tap_key(&mut app, KeyCode::Space);
+    let world = app.world_mut();
+    let player = world.query_filtered::<Entity, With<Player>>().single(world);
+    world.despawn(player);
+    advance(&mut app, 75 * 60);
+
+    let counts = app.world().resource::<Counts>();
+    assert!(counts.peak_active > 0);
+    assert_eq!(counts.created, POOL_PREALLOC, "peak {}", counts.peak_active);
+}
+
+#[test]
+fn ending_a_run_parks_every_obstacle() {
+    let mut app = booted_app(4, &MemoryBackend::default());
+    tap_key(&mut app, KeyCode::Space);
+    advance(&mut app, 5 * 60);
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::GameOver);
+    advance(&mut app, 2);
+    assert_eq!(state(&app), GameState::GameOver);
+
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Obstacle>().iter(world).count(), 0);
+    let hidden = world
+        .query::<(&ObstacleKind, &Visibility)>()
+        .iter(world)
+        .filter(|(_, v)| **v == Visibility::Hidden)
+        .count();
+    assert_eq!(hidden, world.resource::<ObstaclePool>().parked());
+    assert!(hidden >= POOL_PREALLOC);
+}
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use taurc_ui::game::{Obstacle, ObstacleKind, RunStats, Score, SCORE_PER_SECOND};
+use taurc_ui::headless::{advance, boot, headless_app, state, tap_key, DEFAULT_FRAME_STEP};
+use taurc_ui::interpolation::SimPosition;
+use taurc_ui::powerups::{
+    ActiveEffects, PowerUp, PowerUpKind, DOUBLER_FACTOR, POWERUP_CENTRE_Y, SLOW_MO_SPEED,
+};
+use taurc_ui::GameState;
+
+fn running_app() -> App {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    boot(&mut app);
+    tap_key(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Playing);
+    app
+}
+
+fn grant(app: &mut App, kind: PowerUpKind) {
+    app.world_mut().resource_mut::<ActiveEffects>().grant(kind);
+}
+
+fn effects(app: &App) -> &ActiveEffects {
+    app.world().resource::<ActiveEffects>()
+}
+
+fn block_ahead(app: &mut App) {
+    app.world_mut().spawn((
+        SimPosition::new(Vec3::new(0.0, ObstacleKind::Block.centre_y(), -4.0)),
+        Transform::default(),
+        Obstacle,
+        ObstacleKind::Block,
+    ));
+}
+
+fn pickup_at(app: &mut App, kind: PowerUpKind, x: f32, z: f32) -> Entity {
+    app.world_mut()
+        .spawn((
+            SimPosition::new(Vec3::new(x, POWERUP_CENTRE_Y, z)),
+            Transform::default(),
+            PowerUp,
+            kind,
+        ))
+        .id()
+}
+
+#[test]
+fn effects_refill_and_run_out() {
+    let mut effects = ActiveEffects::default();
+    effects.grant(PowerUpKind::Magnet);
+    effects.tick(PowerUpKind::Magnet.secs() - 1.0);
+    effects.grant(PowerUpKind::Magnet);
+    assert_eq!(
+        effects.remaining(PowerUpKind::Magnet),
+        Some(PowerUpKind::Magnet.secs())
+    );
+    effects.grant(PowerUpKind::Doubler);
+    effects.tick(PowerUpKind::Magnet.secs());
+    assert_eq!(effects.iter().count(), 0);
+
+    effects.grant(PowerUpKind::Shield);
+    assert!(effects.consume(PowerUpKind::Shield));
+    assert!(!effects.consume(PowerUpKind::Shield));
+}
+
+#[test]
+fn touching_a_pickup_grants_its_effect() {
+    let mut app = running_app();
+    let pickup = pickup_at(&mut app, PowerUpKind::Shield, 0.0, -4.0);
+    advance(&mut app, 30);
+    assert!(app.world().get_entity(pickup).is_none());
+    assert!(effects(&app).is_active(PowerUpKind::Shield));
+}
+
+#[test]
+fn magnet_pulls_pickups_in() {
+    let mut app = running_app();
+    grant(&mut app, PowerUpKind::Magnet);
+    let pickup = pickup_at(&mut app, PowerUpKind::Doubler, 2.0, -10.0);
+    advance(&mut app, 40);
+    assert!(app.world().get_entity(pickup).is_none());
+    assert!(effects(&app).is_active(PowerUpKind::Doubler));
+}
+
+#[test]
+fn shield_absorbs_one_hit() {
+    let mut app = running_app();
+    grant(&mut app, PowerUpKind::Shield);
+    block_ahead(&mut app);
+    advance(&mut app, 30);
+    assert_eq!(state(&app), GameState::Playing);
+    assert!(!effects(&app).is_active(PowerUpKind::Shield));
+
+    block_ahead(&mut app);
+    advance(&mut app, 30);
+    assert_eq!(state(&app), GameState::GameOver);
+}
+
+#[test]
+fn slow_motion_scales_virtual_time() {
+    let mut app = running_app();
+    grant(&mut app, PowerUpKind::SlowMo);
+    let before = app.world().resource::<RunStats>().elapsed;
+    advance(&mut app, 60);
+    let simulated = app.world().resource::<RunStats>().elapsed - before;
+    assert!((simulated - SLOW_MO_SPEED).abs() < 0.05, "{simulated}");
+
+    // Leaving the run puts time back to normal.
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::GameOver);
+    advance(&mut app, 2);
+    assert_eq!(
+        app.world().resource::<Time<Virtual>>().relative_speed(),
+        1.0
+    );
+}
+
+#[test]
+fn doubler_doubles_score_but_not_difficulty() {
+    let mut app = running_app();
+    grant(&mut app, PowerUpKind::Doubler);
+    let value = app.world().resource::<Score>().value;
+    let stats = *app.world().resource::<RunStats>();
+    advance(&mut app, 60);
+    let gained = app.world().resource::<Score>().value - value;
+    let after = app.world().resource::<RunStats>();
+    let secs = after.elapsed - stats.elapsed;
+    assert!((gained - secs * SCORE_PER_SECOND * DOUBLER_FACTOR).abs() < 0.01);
+}
@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+use taurc_ui::game::Score;
+use taurc_ui::headless::{
+    advance, boot, booted_app, headless_app, run_until_game_over, started_app, state, tap_key,
+    DEFAULT_FRAME_STEP,
+};
+use taurc_ui::profile::{Profile, RunHistory, RunSummary, HISTORY_LEN, PROFILE_KEY};
+use taurc_ui::storage::{MemoryBackend, PendingLoad, SaveBackend, SaveStore};
+use taurc_ui::GameState;
+
+const SEED: u64 = 77;
+
+fn app_with_store(backend: impl SaveBackend) -> App {
+    let mut app = headless_app(DEFAULT_FRAME_STEP);
+    app.insert_resource(SaveStore::new(backend));
+    app
+}
+
+fn saved_profile(backend: &MemoryBackend) -> Profile {
+    serde_json::from_str(&backend.get(PROFILE_KEY).expect("profile saved")).unwrap()
+}
+
+#[test]
+fn saved_profile_is_loaded_before_menu() {
+    let backend = MemoryBackend::default();
+    let profile = Profile {
+        best: 321.0,
+        history: vec![RunSummary {
+            score: 321.0,
+            seed: 9,
+            seconds: 32.1,
+        }],
+        ..Default::default()
+    };
+    backend.insert(PROFILE_KEY, serde_json::to_string(&profile).unwrap());
+
+    let mut app = app_with_store(backend);
+    app.update();
+    assert_eq!(state(&app), GameState::Loading);
+    boot(&mut app);
+    assert_eq!(app.world().resource::<Score>().best, 321.0);
+    assert_eq!(app.world().resource::<RunHistory>().0, profile.history);
+}
+
+#[test]
+fn finished_run_is_saved() {
+    let backend = MemoryBackend::default();
+    let mut app = started_app(SEED, &backend);
+    run_until_game_over(&mut app);
+
+    let score = app.world().resource::<Score>();
+    let saved = saved_profile(&backend);
+    assert_eq!(saved.best, score.best);
+    assert_eq!(saved.history.len(), 1);
+    assert_eq!(saved.history[0].seed, SEED);
+    assert_eq!(saved.history[0].score, score.value);
+
+    // A fresh session picks the best back up.
+    let next_session = booted_app(SEED, &backend);
+    assert_eq!(next_session.world().resource::<Score>().best, saved.best);
+}
+
+#[test]
+fn history_is_capped() {
+    let backend = MemoryBackend::default();
+    let history = (0..HISTORY_LEN as u64 + 5)
+        .map(|seed| RunSummary {
+            score: 1.0,
+            seed,
+            seconds: 0.1,
+        })
+        .collect();
+    backend.insert(
+        PROFILE_KEY,
+        serde_json::to_string(&Profile {
+            best: 1.0,
+            history,
+            ..Default::default()
+        })
+        .unwrap(),
+    );
+    let mut app = app_with_store(backend);
+    boot(&mut app);
+    assert_eq!(app.world().resource::<RunHistory>().0.len(), HISTORY_LEN);
+}
+
+#[test]
+fn unreadable_profile_falls_back_to_defaults() {
+    let backend = MemoryBackend::default();
+    backend.insert(PROFILE_KEY, "{ not json");
+    let mut app = app_with_store(backend);
+    boot(&mut app);
+    assert_eq!(app.world().resource::<Score>().best, 0.0);
+}
+
+#[test]
+fn unreadable_profile_is_not_overwritten() {
+    let backend = MemoryBackend::default();
+    // E.g. written by a newer build with a field this one can't parse.
+    let unreadable = r#"{"best": 900.0, "history": "from the future"}"#;
+    backend.insert(PROFILE_KEY, unreadable);
+    let mut app = started_app(SEED, &backend);
+    run_until_game_over(&mut app);
+    assert_eq!(backend.get(PROFILE_KEY).as_deref(), Some(unreadable));
+}
+
+/// A backend whose loads never complete, like an unresponsive IPC bridge.
+struct StalledBackend;
+
+impl SaveBackend for StalledBackend {
+    fn name(&self) -> &'static str {
+        "stalled"
+    }
+
+    fn load(&self, _key: &str) -> PendingLoad {
+        PendingLoad::default()
+    }
+
+    fn store(&self, _key: &str, _data: String) {}
+}
+
+/// Stores into `0`, but its loads never complete.
+struct StalledLoadBackend(MemoryBackend);
+
+impl SaveBackend for StalledLoadBackend {
+    fn name(&self) -> &'static str {
+        "stalled load"
+    }
+
+    fn load(&self, _key: &str) -> PendingLoad {
+        PendingLoad::default()
+    }
+
+    fn store(&self, key: &str, data: String) {
+        self.0.store(key, data);
+    }
+}
+
+#[test]
+fn timed_out_load_does_not_overwrite_the_save() {
+    let backend = MemoryBackend::default();
+    let profile = serde_json::to_string(&Profile {
+        best: 321.0,
+        ..Default::default()
+    })
+    .unwrap();
+    backend.insert(PROFILE_KEY, profile.clone());
+    let mut app = app_with_store(StalledLoadBackend(backend.clone()));
+    boot(&mut app);
+    tap_key(&mut app, KeyCode::Space);
+    run_until_game_over(&mut app);
+    assert_eq!(backend.get(PROFILE_KEY), Some(profile));
+}
+
+#[test]
+fn stalled_load_times_out_into_menu() {
+    let mut app = app_with_store(StalledBackend);
+    for _ in 0..60 {
+        app.update();
+    }
+    assert_eq!(state(&app), GameState::Loading);
+    advance(&mut app, 5 * 60);
+    boot(&mut app);
+}
@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use std::time::Duration;
+use taurc_ui::difficulty::Difficulty;
+use taurc_ui::game::{RunSeed, Score, VerticalAction};
+use taurc_ui::headless::{
+    boot, booted_app, headless_app, run_until_game_over, started_app, state, tap_key,
+    DEFAULT_FRAME_STEP, MAX_FRAMES,
+};
+use taurc_ui::lanes::ControlScheme;
+use taurc_ui::replay::{InputRecording, LastRun, TickInput};
+use taurc_ui::storage::MemoryBackend;
+use taurc_ui::GameState;
+
+const SEED: u64 = 42;
+
+/// Plays a live run under the given settings that weaves left and right and
+/// jumps now and then, returning its recording and final score.
+fn play_weaving_run(difficulty: Difficulty, controls: ControlScheme) -> (InputRecording, f32) {
+    let mut app = booted_app(SEED, &MemoryBackend::default());
+    app.insert_resource(difficulty).insert_resource(controls);
+    tap_key(&mut app, KeyCode::Space);
+    let keys = [
+        KeyCode::ArrowLeft,
+        KeyCode::ArrowLeft,
+        KeyCode::ArrowUp,
+        KeyCode::ArrowRight,
+        KeyCode::ArrowRight,
+    ];
+    for frame in 0..MAX_FRAMES {
+        if state(&app) == GameState::GameOver {
+            break;
+        }
+        if frame % 17 == 0 {
+            tap_key(&mut app, keys[(frame / 17) as usize % keys.len()]);
+        }
+        app.update();
+    }
+    assert_eq!(state(&app), GameState::GameOver);
+    let recording = app
+        .world()
+        .resource::<LastRun>()
+        .0
+        .clone()
+        .expect("run recorded");
+    (recording, app.world().resource::<Score>().value)
+}
+
+fn replay(recording: &InputRecording, frame_step: Duration) -> f32 {
+    let mut app = headless_app(frame_step);
+    boot(&mut app);
+    app.insert_resource(LastRun(Some(recording.clone())));
+    app.insert_resource(RunSeed(recording.seed));
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Replay);
+    app.update();
+    assert_eq!(state(&app), GameState::Replay);
+    run_until_game_over(&mut app);
+    app.world().resource::<Score>().value
+}
+
+#[test]
+fn recording_round_trips_through_text() {
+    let mut recording = InputRecording::new(987_654_321);
+    for x in [0.0, 0.0, 0.0, -0.9, -0.9, 1.8, 0.1 + 0.2] {
+        recording.push(x);
+    }
+    assert_eq!(recording.ticks(), 7);
+    let text = recording.encode();
+    assert_eq!(InputRecording::decode(&text), Some(recording.clone()));
+    assert_eq!(
+        recording.iter().map(|i| i.target_x).collect::<Vec<_>>(),
+        vec![0.0, 0.0, 0.0, -0.9, -0.9, 1.8, 0.1 + 0.2]
+    );
+    assert!(InputRecording::decode("not a recording").is_none());
+    assert!(InputRecording::decode("1:zz*3").is_none());
+}
+
+#[test]
+fn actions_round_trip_through_text() {
+    let mut recording = InputRecording::new(5);
+    let jump = TickInput {
+        target_x: 1.8,
+        action: Some(VerticalAction::Jump),
+    };
+    let slide = TickInput {
+        target_x: -0.9,
+        action: Some(VerticalAction::Slide),
+    };
+    for input in [TickInput::from(1.8), jump, TickInput::from(1.8), slide] {
+        recording.push(input);
+    }
+    let text = recording.encode();
+    assert_eq!(
+        text,
+        format!(
+            "v2:5:normal:analog:{x:08x}*1,{x:08x}j*1,{x:08x}*1,{y:08x}s*1",
+            x = 1.8_f32.to_bits(),
+            y = (-0.9_f32).to_bits()
+        )
+    );
+    let decoded = InputRecording::decode(&text).unwrap();
+    assert_eq!(
+        decoded.iter().collect::<Vec<_>>(),
+        vec![TickInput::from(1.8), jump, TickInput::from(1.8), slide]
+    );
+}
+
+#[test]
+fn settings_round_trip_through_text() {
+    let mut recording =
+        InputRecording::new(77).with_settings(Difficulty::Hard, ControlScheme::FiveLanes);
+    recording.push(0.0);
+    let text = recording.encode();
+    assert_eq!(text, "v2:77:hard:lanes5:00000000*1");
+    assert_eq!(InputRecording::decode(&text), Some(recording));
+    assert!(InputRecording::decode("v2:77:impossible:lanes5:00000000*1").is_none());
+    assert!(InputRecording::decode("v2:77:hard").is_none());
+
+    // Text from before settings were recorded plays under the defaults.
+    let old = InputRecording::decode("77:00000000*1").unwrap();
+    assert_eq!(old.seed, 77);
+    assert_eq!(old.difficulty, Difficulty::Normal);
+    assert_eq!(old.controls, ControlScheme::Analog);
+}
+
+#[test]
+fn steady_input_is_compact() {
+    let mut recording = InputRecording::new(1);
+    for _ in 0..10_000 {
+        recording.push(2.7);
+    }
+    assert_eq!(recording.ticks(), 10_000);
+    assert_eq!(
+        recording.encode(),
+        format!("v2:1:normal:analog:{:08x}*10000", 2.7_f32.to_bits())
+    );
+}
+
+#[test]
+fn replay_reproduces_live_run() {
+    let (recording, live_score) = play_weaving_run(Difficulty::Normal, ControlScheme::Analog);
+    assert!(
+        recording.iter().any(|i| i.target_x != 0.0),
+        "run should have steered"
+    );
+    assert!(
+        recording.iter().any(|i| i.action.is_some()),
+        "run should have jumped"
+    );
+    assert_eq!(replay(&recording, DEFAULT_FRAME_STEP), live_score);
+}
+
+#[test]
+fn replay_reproduces_live_run_at_other_frame_rates() {
+    let (recording, live_score) = play_weaving_run(Difficulty::Normal, ControlScheme::Analog);
+    let recording = InputRecording::decode(&recording.encode()).unwrap();
+    for hz in [30.0, 144.0] {
+        assert_eq!(
+            replay(&recording, Duration::from_secs_f64(1.0 / hz)),
+            live_score,
+            "replay diverged at {hz} Hz"
+        );
+    }
+}
+
+#[test]
+fn watch_replay_from_game_over() {
+    let mut app = started_app(SEED, &MemoryBackend::default());
+    run_until_game_over(&mut app);
+    let live_score = app.world().resource::<Score>().value;
+
+    app.insert_resource(RunSeed(7));
+    tap_key(&mut app, KeyCode::KeyV);
+    assert_eq!(state(&app), GameState::Replay);
+    assert_eq!(app.world().resource::<RunSeed>().0, SEED);
+    run_until_game_over(&mut app);
+    assert_eq!(app.world().resource::<Score>().value, live_score);
+}
+
+#[test]
+fn replay_uses_the_recorded_settings() {
+    let (recording, live_score) = play_weaving_run(Difficulty::Hard, ControlScheme::ThreeLanes);
+    let recording = InputRecording::decode(&recording.encode()).unwrap();
+    assert_eq!(recording.difficulty, Difficulty::Hard);
+    assert_eq!(recording.controls, ControlScheme::ThreeLanes);
+
+    // Watched from an app left on the defaults.
+    let mut app = booted_app(SEED, &MemoryBackend::default());
+    app.insert_resource(LastRun(Some(recording.clone())));
+    app.insert_resource(RunSeed(recording.seed));
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Replay);
+    app.update();
+    assert_eq!(*app.world().resource::<Difficulty>(), Difficulty::Hard);
+    assert_eq!(
+        *app.world().resource::<ControlScheme>(),
+        ControlScheme::ThreeLanes
+    );
+    run_until_game_over(&mut app);
+    assert_eq!(app.world().resource::<Score>().value, live_score);
+
+    // The player's own settings are back afterwards.
+    assert_eq!(*app.world().resource::<Difficulty>(), Difficulty::Normal);
+    assert_eq!(
+        *app.world().resource::<ControlScheme>(),
+        ControlScheme::Analog
+    );
+}
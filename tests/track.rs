@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use taurc_ui::game::{Player, OBSTACLE_DESPAWN_Z, OBSTACLE_START_Z, PLAYER_SIZE};
+use taurc_ui::headless::{advance, booted_app, state, tap_key};
+use taurc_ui::interpolation::SimPosition;
+use taurc_ui::lanes::ControlScheme;
+use taurc_ui::storage::MemoryBackend;
+use taurc_ui::track::{
+    lane_markings, scenery, TrackSegment, SCENERY_MIN_X, TRACK_SEGMENTS, TRACK_SEGMENT_LEN,
+    TRACK_WIDTH,
+};
+use taurc_ui::GameState;
+
+fn segments(app: &mut App) -> Vec<(Entity, f32, u32)> {
+    let world = app.world_mut();
+    let mut found: Vec<_> = world
+        .query::<(Entity, &SimPosition, &TrackSegment)>()
+        .iter(world)
+        .map(|(e, pos, segment)| (e, pos.current.z, segment.index))
+        .collect();
+    found.sort_by(|a, b| b.1.total_cmp(&a.1));
+    found
+}
+
+/// Ground under every z from behind the player to past the spawn line, with
+/// no gaps or overlaps between segments.
+fn assert_covered(found: &[(Entity, f32, u32)]) {
+    assert_eq!(found.len(), TRACK_SEGMENTS as usize);
+    for pair in found.windows(2) {
+        assert!((pair[0].1 - pair[1].1 - TRACK_SEGMENT_LEN).abs() < 1e-3);
+        assert_eq!(pair[1].2, pair[0].2 + 1, "far segments have later indices");
+    }
+    let near = found[0].1 + TRACK_SEGMENT_LEN * 0.5;
+    let far = found[found.len() - 1].1 - TRACK_SEGMENT_LEN * 0.5;
+    assert!(near >= OBSTACLE_DESPAWN_Z, "near end at {near}");
+    assert!(far <= OBSTACLE_START_Z, "far end at {far}");
+}
+
+#[test]
+fn the_track_streams_by_recycling_its_segments() {
+    let mut app = booted_app(8, &MemoryBackend::default());
+    tap_key(&mut app, KeyCode::Space);
+    let world = app.world_mut();
+    let player = world.query_filtered::<Entity, With<Player>>().single(world);
+    world.despawn(player);
+
+    let start = segments(&mut app);
+    assert_covered(&start);
+    for _ in 0..30 {
+        advance(&mut app, 60);
+        assert_covered(&segments(&mut app));
+    }
+
+    let end = segments(&mut app);
+    let mut entities: Vec<_> = end.iter().map(|s| s.0).collect();
+    let mut first: Vec<_> = start.iter().map(|s| s.0).collect();
+    entities.sort();
+    first.sort();
+    assert_eq!(entities, first, "segments are reused, not respawned");
+    assert!(end[0].2 >= TRACK_SEGMENTS, "nothing was recycled");
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Menu);
+    advance(&mut app, 2);
+    assert_eq!(state(&app), GameState::Menu);
+    assert!(segments(&mut app).is_empty());
+}
+
+#[test]
+fn the_ground_stops_while_paused_and_goes_with_the_run() {
+    let mut app = booted_app(8, &MemoryBackend::default());
+    tap_key(&mut app, KeyCode::Space);
+    let start = segments(&mut app);
+    advance(&mut app, 10);
+    assert!(
+        segments(&mut app)[0].1 > start[0].1,
+        "the ground isn't moving"
+    );
+
+    tap_key(&mut app, KeyCode::KeyP);
+    let frozen = segments(&mut app);
+    advance(&mut app, 30);
+    assert_eq!(segments(&mut app), frozen);
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::GameOver);
+    advance(&mut app, 2);
+    assert!(segments(&mut app).is_empty());
+}
+
+#[test]
+fn scenery_is_stable_and_off_the_track() {
+    for index in 0..50 {
+        let props = scenery(index);
+        assert_eq!(props, scenery(index));
+        for prop in &props {
+            assert!(prop.offset.x.abs() >= SCENERY_MIN_X);
+            assert!(prop.offset.x.abs() > TRACK_WIDTH * 0.5);
+            assert!(prop.offset.z.abs() <= TRACK_SEGMENT_LEN * 0.5);
+            assert!(prop.height > 0.0);
+        }
+    }
+    assert_ne!(scenery(0), scenery(1));
+}
+
+#[test]
+fn lane_lines_fall_between_lane_centres() {
+    assert_eq!(lane_markings(ControlScheme::Analog).len(), 2);
+    for scheme in [ControlScheme::ThreeLanes, ControlScheme::FiveLanes] {
+        let lanes = scheme.lanes().unwrap();
+        let xs = lane_markings(scheme);
+        assert_eq!(xs.len(), 2 + lanes.count() - 1);
+        for (i, x) in xs[2..].iter().enumerate() {
+            assert!(*x > lanes.centre(i) && *x < lanes.centre(i + 1));
+        }
+        // The edges are outside anywhere the player can stand.
+        let reach = lanes.centre(lanes.count() - 1) + PLAYER_SIZE.x * 0.5;
+        assert!(xs[1] >= reach && xs[0] <= -reach);
+    }
+}
@@ -0,0 +1,137 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashSet;
+use taurc_ui::collision::hit_half_extents;
+use taurc_ui::game::{ObstacleKind, OBSTACLE_SIZE, PLAYER_SIZE, TRACK_HALF_X};
+use taurc_ui::lanes::Lanes;
+use taurc_ui::waves::{pick, tier, WavePattern, MAX_TIER, PATTERNS, TIER_SECS};
+
+const ALL: [WavePattern; 7] = [
+    WavePattern::Single,
+    WavePattern::WallWithGap,
+    WavePattern::ZigZag,
+    WavePattern::StaggeredPair,
+    WavePattern::ClosingCorridor,
+    WavePattern::HurdleRow,
+    WavePattern::OverheadBar,
+];
+
+/// Player X positions on the track that clear every cube in `row`.
+fn open_xs(row: &[f32]) -> Vec<f32> {
+    let reach = hit_half_extents(PLAYER_SIZE, OBSTACLE_SIZE).x;
+    (0..=840)
+        .map(|i| -TRACK_HALF_X + i as f32 * 0.01)
+        .filter(|x| row.iter().all(|cube| (x - cube).abs() >= reach))
+        .collect()
+}
+
+#[test]
+fn layouts_stay_on_the_track() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    for pattern in ALL {
+        for _ in 0..200 {
+            for offset in pattern.layout(&mut rng) {
+                assert!(
+                    offset.x.abs() <= TRACK_HALF_X + 1e-4,
+                    "{pattern:?} {offset}"
+                );
+                assert!(offset.y >= 0.0, "{pattern:?} {offset}");
+            }
+        }
+    }
+}
+
+#[test]
+fn every_wall_row_leaves_one_opening() {
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+    for pattern in [WavePattern::WallWithGap, WavePattern::ClosingCorridor] {
+        for _ in 0..200 {
+            let layout = pattern.layout(&mut rng);
+            let depths: HashSet<u32> = layout.iter().map(|o| o.y.to_bits()).collect();
+            for depth in depths {
+                let row: Vec<f32> = layout
+                    .iter()
+                    .filter(|o| o.y.to_bits() == depth)
+                    .map(|o| o.x)
+                    .collect();
+                let open = open_xs(&row);
+                assert!(!open.is_empty(), "{pattern:?} row {row:?} is closed");
+                let contiguous = open.windows(2).all(|w| w[1] - w[0] < 0.011);
+                assert!(contiguous, "{pattern:?} row {row:?} has two openings");
+            }
+        }
+    }
+}
+
+#[test]
+fn hurdle_and_barrier_rows_close_the_whole_track() {
+    let mut rng = ChaCha8Rng::seed_from_u64(4);
+    for pattern in [WavePattern::HurdleRow, WavePattern::OverheadBar] {
+        assert_ne!(pattern.kind(), ObstacleKind::Block);
+        let row: Vec<f32> = pattern.layout(&mut rng).iter().map(|o| o.x).collect();
+        let reach = hit_half_extents(PLAYER_SIZE, pattern.kind().size()).x;
+        let open = (0..=840)
+            .map(|i| -TRACK_HALF_X + i as f32 * 0.01)
+            .any(|x| row.iter().all(|cube| (x - cube).abs() >= reach));
+        assert!(!open, "{pattern:?} can be steered around");
+    }
+}
+
+#[test]
+fn picks_respect_tier() {
+    let mut rng = ChaCha8Rng::seed_from_u64(3);
+    for t in 0..=MAX_TIER {
+        let unlocked: HashSet<WavePattern> = PATTERNS
+            .iter()
+            .filter(|p| p.min_tier <= t)
+            .map(|p| p.pattern)
+            .collect();
+        let seen: HashSet<WavePattern> = (0..2000).map(|_| pick(&mut rng, t)).collect();
+        assert_eq!(seen, unlocked, "tier {t}");
+    }
+}
+
+#[test]
+fn tier_rises_with_time_and_caps() {
+    assert_eq!(tier(0.0, 0), 0);
+    assert_eq!(tier(TIER_SECS * 1.5, 0), 1);
+    assert_eq!(tier(0.0, 1), 1);
+    assert_eq!(tier(TIER_SECS * 100.0, 0), MAX_TIER);
+}
+
+#[test]
+fn same_rng_same_waves() {
+    let waves = |seed| {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        (0..50)
+            .map(|i| pick(&mut rng, i % (MAX_TIER + 1)).layout(&mut rng))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(waves(7), waves(7));
+    assert_ne!(waves(7), waves(8));
+}
+
+#[test]
+fn lane_layouts_sit_on_lane_centres_and_leave_a_lane_open() {
+    let mut rng = ChaCha8Rng::seed_from_u64(4);
+    for lanes in [Lanes(3), Lanes(5)] {
+        let centres: Vec<f32> = (0..lanes.count()).map(|l| lanes.centre(l)).collect();
+        for pattern in ALL {
+            for _ in 0..200 {
+                let layout = pattern.lane_layout(&mut rng, lanes);
+                assert!(!layout.is_empty(), "{pattern:?}");
+                if pattern.kind() != ObstacleKind::Block {
+                    continue;
+                }
+                for offset in &layout {
+                    assert!(
+                        centres.iter().any(|c| (c - offset.x).abs() < 1e-4),
+                        "{pattern:?} {offset} off-lane in {lanes:?}"
+                    );
+                    let row = layout.iter().filter(|o| o.y == offset.y).count();
+                    assert!(row < lanes.count(), "{pattern:?} closes every lane");
+                }
+            }
+        }
+    }
+}